@@ -110,5 +110,5 @@ fn run(filename: &str, round: usize, enable_parallel: bool, free_space: usize, e
 }
 
 fn error<S: Into<String>>(message: S) -> io::Error {
-    io::Error::new(io::ErrorKind::Other, message.into())
+    io::Error::other(message.into())
 }
@@ -142,7 +142,7 @@ fn run(filename: &str, round: usize, enable_parallel: bool, free_space: usize, e
 }
 
 fn validate_suffix_array(text: &[u8], suffix_array: &[i32]) -> bool {
-    if text.len() > 0 {
+    if !text.is_empty() {
         if suffix_array.len() < text.len() {
             return false;
         }
@@ -156,7 +156,7 @@ fn validate_suffix_array(text: &[u8], suffix_array: &[i32]) -> bool {
 }
 
 fn validate_lcp_array(text: &[u8], suffix_array: &[i32], lcp_array: &[i32]) -> bool {
-    if text.len() > 0 {
+    if !text.is_empty() {
         if (suffix_array.len() < text.len()) || (lcp_array.len() < text.len()) || lcp_array[0] != 0 {
             return false;
         }
@@ -173,5 +173,5 @@ fn validate_lcp_array(text: &[u8], suffix_array: &[i32], lcp_array: &[i32]) -> b
 }
 
 fn error<S: Into<String>>(message: S) -> io::Error {
-    io::Error::new(io::ErrorKind::Other, message.into())
+    io::Error::other(message.into())
 }
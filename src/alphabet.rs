@@ -0,0 +1,153 @@
+//! Suffix array construction over arbitrary ordered symbols, e.g. sparse `u32`/`u64`/`i64` token ids.
+//!
+//! Symbols are replaced by their ranks in the sorted alphabet, which keeps the suffix order, and the remapped
+//! text goes to the narrowest backend able to hold the ranks: [`sais32::sais`](crate::sais32::sais) for bytes,
+//! `sais16::sais` for 16-bit ranks if the `sais16` feature is enabled, and [`sais_int`](crate::sais32::sais_int)
+//! otherwise. The input text is never modified.
+
+use crate::common::max_size;
+use crate::errors::Error;
+use crate::sais32::{Result, MAX_LENGTH};
+
+/// Backend used for the remapped text.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Backend {
+    /// `sais32::sais` on `u8` ranks.
+    Sais32,
+    /// `sais16::sais` on `u16` ranks.
+    Sais16,
+    /// `sais32::sais_int` on `i32` ranks.
+    SaisInt,
+}
+
+/// Sorted distinct symbols of a text.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "Vec<S>",
+        into = "Vec<S>",
+        bound(serialize = "S: Clone + serde::Serialize", deserialize = "S: Ord + serde::Deserialize<'de>")
+    )
+)]
+pub struct Alphabet<S> {
+    symbols: Vec<S>,
+}
+
+#[cfg(feature = "serde")]
+impl<S: Ord> TryFrom<Vec<S>> for Alphabet<S> {
+    type Error = &'static str;
+
+    fn try_from(symbols: Vec<S>) -> std::result::Result<Self, Self::Error> {
+        match symbols.windows(2).all(|pair| pair[0] < pair[1]) {
+            true => Ok(Alphabet { symbols }),
+            false => Err("alphabet symbols not sorted or not distinct"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S> From<Alphabet<S>> for Vec<S> {
+    fn from(alphabet: Alphabet<S>) -> Self {
+        alphabet.symbols
+    }
+}
+
+impl<S: Copy + Ord> Alphabet<S> {
+    /// Collects the alphabet of `t`.
+    pub fn new(t: &[S]) -> Self {
+        let mut symbols = t.to_vec();
+        symbols.sort_unstable();
+        symbols.dedup();
+        Alphabet { symbols }
+    }
+
+    /// Number of distinct symbols.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Whether the alphabet is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Distinct symbols in ascending order, indexed by rank.
+    #[inline]
+    pub fn symbols(&self) -> &[S] {
+        &self.symbols
+    }
+
+    /// Rank of symbol `s`, or `None` if it is not in the alphabet.
+    #[inline]
+    pub fn rank(&self, s: S) -> Option<usize> {
+        self.symbols.binary_search(&s).ok()
+    }
+
+    /// Narrowest backend able to hold ranks of the alphabet.
+    pub fn backend(&self) -> Backend {
+        match self.len() {
+            0..=256 => Backend::Sais32,
+            #[cfg(feature = "sais16")]
+            257..=65536 => Backend::Sais16,
+            _ => Backend::SaisInt,
+        }
+    }
+
+    /// Constructs suffix array of `t`, whose symbols must all be in the alphabet, returns the backend used.
+    ///
+    /// Returns `Err(Error::IllegalArguments)` if `t` has symbols not in the alphabet,
+    /// or `sa` is shorter than `t`.
+    pub fn sais(&self, t: &[S], sa: &mut [i32]) -> Result<Backend> {
+        max_size(t.len(), MAX_LENGTH)?;
+        let backend = self.backend();
+        let rank = |&s: &S| self.rank(s).ok_or(Error::IllegalArguments);
+        match backend {
+            Backend::Sais32 => {
+                let t = t
+                    .iter()
+                    .map(|s| rank(s).map(|r| r as u8))
+                    .collect::<Result<Vec<_>>>()?;
+                crate::sais32::sais(&t, sa, None)?
+            }
+            #[cfg(feature = "sais16")]
+            Backend::Sais16 => {
+                let t = t
+                    .iter()
+                    .map(|s| rank(s).map(|r| r as u16))
+                    .collect::<Result<Vec<_>>>()?;
+                crate::sais16::sais(&t, sa, None)?
+            }
+            _ => {
+                let mut t = t
+                    .iter()
+                    .map(|s| rank(s).map(|r| r as i32))
+                    .collect::<Result<Vec<_>>>()?;
+                crate::sais32::sais_int(&mut t, sa, self.len() as i32)?
+            }
+        }
+        Ok(backend)
+    }
+}
+
+/// Constructs suffix array of a text over arbitrary ordered symbols, returns the backend used.
+///
+/// Returns `Err(Error::IllegalArguments)` if `sa` is shorter than `t`.
+///
+/// # Examples
+///
+/// ```
+/// use sais::alphabet::{sais_compact, Backend};
+///
+/// let t: [u64; 6] = [1 << 40, 7, 1 << 40, 7, 1 << 40, 3];
+/// let mut sa = vec![0; t.len()];
+/// assert_eq!(sais_compact(&t, &mut sa), Ok(Backend::Sais32));
+/// assert_eq!(sa, [5, 3, 1, 4, 2, 0]);
+/// ```
+pub fn sais_compact<S: Copy + Ord>(t: &[S], sa: &mut [i32]) -> Result<Backend> {
+    Alphabet::new(t).sais(t, sa)
+}
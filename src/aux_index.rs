@@ -70,10 +70,11 @@ pub fn aux_length_max(text_size: usize, aux_capacity: usize) -> Option<usize> {
 pub fn aux_rate_min(text_size: usize, aux_capacity: usize) -> Option<usize> {
     if aux_capacity == 0 {
         None
-    } else if let Some(aux_rate) = div_ceil(text_size, aux_capacity).checked_next_power_of_two() {
-        Some(Ord::max(aux_rate, AUX_RATE_MIN))
     } else {
-        None
+        text_size
+            .div_ceil(aux_capacity)
+            .checked_next_power_of_two()
+            .map(|aux_rate| Ord::max(aux_rate, AUX_RATE_MIN))
     }
 }
 
@@ -145,19 +146,8 @@ pub fn aux_rate_exact(text_size: usize, exact_aux_length: usize) -> Option<usize
 #[inline]
 pub fn aux_length_exact(text_size: usize, aux_rate: usize) -> Option<usize> {
     if aux_rate >= AUX_RATE_MIN && aux_rate.is_power_of_two() {
-        Some(Ord::max(div_ceil(text_size, aux_rate), AUX_LENGTH_MIN))
+        Some(Ord::max(text_size.div_ceil(aux_rate), AUX_LENGTH_MIN))
     } else {
         None
     }
 }
-
-#[inline]
-fn div_ceil(x: usize, y: usize) -> usize {
-    // use usize::div_ceil when it comes to stable rust
-    // do not check y != 0
-    if x % y == 0 {
-        x / y
-    } else {
-        x / y + 1
-    }
-}
@@ -1,5 +1,6 @@
 use crate::aux_index::aux_rate_exact;
 use crate::errors::{Error, ReturnCode};
+use crate::index::SaIndex;
 
 #[inline]
 pub unsafe fn freq_as_mut_ptr<T, EI: ReturnCode>(freq: Option<&mut [T]>, size: usize) -> Result<*mut T, Error<EI>> {
@@ -58,7 +59,7 @@ pub fn unbwt_sufficient_size<T: TryFrom<usize>, EI: ReturnCode>(text_size: usize
     if tmp_size <= text_size {
         Err(Error::IllegalArguments)?
     }
-    Ok(text_size.try_into().map_err(|_| Error::InternalError)?)
+    text_size.try_into().map_err(|_| Error::InternalError)
 }
 
 #[inline]
@@ -91,3 +92,205 @@ pub fn interpret_return_code_64(code: i64) -> Result<i64, Error<i64>> {
         err => Err(Error::Uncategorized(err)),
     }
 }
+
+#[inline]
+pub fn inverse_suffix_array<I: SaIndex>(sa: &[I]) -> Result<Vec<I>, Error<I>> {
+    let n = max_size(sa.len(), I::MAX_LENGTH)?;
+    let mut isa = vec![I::default(); n];
+    let mut seen = vec![false; n];
+    for (rank, &suffix) in sa.iter().enumerate() {
+        if suffix < I::default() || suffix.as_usize() >= n || seen[suffix.as_usize()] {
+            Err(Error::IllegalArguments)?
+        }
+        seen[suffix.as_usize()] = true;
+        isa[suffix.as_usize()] = I::from_usize(rank);
+    }
+    Ok(isa)
+}
+
+#[inline]
+#[cfg(feature = "sais32")]
+pub fn lcp_array<T: Eq, I: SaIndex>(t: &[T], sa: &[I]) -> Result<Vec<I>, Error<I>> {
+    same_size(t.len(), sa.len())?;
+    let isa = inverse_suffix_array(sa)?;
+    let mut lcp = vec![I::default(); t.len()];
+    let mut common = 0usize;
+    for (p, &rank) in isa.iter().enumerate() {
+        let rank = rank.as_usize();
+        if rank == 0 {
+            common = 0;
+            continue;
+        }
+        let q = sa[rank - 1].as_usize();
+        while p + common < t.len() && q + common < t.len() && t[p + common] == t[q + common] {
+            common += 1;
+        }
+        lcp[rank] = I::from_usize(common);
+        common = common.saturating_sub(1);
+    }
+    Ok(lcp)
+}
+
+/// Derives bwt in libsais layout from suffix array, returns the primary index.
+///
+/// Row `0` of the conceptual matrix is the sentinel suffix and row `k + 1` is suffix `sa[k]`,
+/// the sentinel symbol at the primary row is left out. `aux[j]` receives the row of suffix `j * rate`.
+#[inline]
+pub fn bwt_from_sa<T: Copy, I: SaIndex>(t: &[T], sa: &[I], u: &mut [T], aux: Option<(&mut [I], usize)>) -> Result<usize, Error<I>> {
+    let n = max_size(same_size(same_size(t.len(), sa.len())?, u.len())?, I::MAX_LENGTH - 1)?;
+    let mut seen = vec![false; n];
+    let mut primary = 0;
+    let mut aux = aux;
+    let mut row = 0;
+    if n > 0 {
+        u[0] = t[n - 1];
+        row = 1;
+    }
+    for (k, &p) in sa.iter().enumerate() {
+        if p < I::default() || p.as_usize() >= n || seen[p.as_usize()] {
+            Err(Error::IllegalArguments)?
+        }
+        let p = p.as_usize();
+        seen[p] = true;
+        if let Some((aux, rate)) = aux.as_mut() {
+            if p % *rate == 0 {
+                aux[p / *rate] = I::from_usize(k + 1);
+            }
+        }
+        if p == 0 {
+            primary = k + 1;
+        } else {
+            u[row] = t[p - 1];
+            row += 1;
+        }
+    }
+    if let Some((aux, _)) = aux {
+        if n == 0 {
+            aux[0] = I::default();
+        }
+    }
+    Ok(primary)
+}
+
+/// Computes LF mapping of all `n + 1` rows of bwt in libsais layout, the primary row maps to row `0`.
+///
+/// Returns `Err(Error::IllegalArguments)` if `freq` does not match symbol frequencies of `bwt`.
+#[inline]
+pub fn lf_mapping<T: Copy + Into<usize>, I: SaIndex>(bwt: &[T], primary: usize, freq: &[I]) -> Result<Vec<I>, Error<I>> {
+    let n = max_size(bwt.len(), I::MAX_LENGTH - 1)?;
+
+    // first row of each symbol, after the sentinel row
+    let mut first = Vec::with_capacity(freq.len());
+    let mut total = 1;
+    for &f in freq {
+        if f < I::default() {
+            Err(Error::IllegalArguments)?
+        }
+        first.push(total);
+        total += f.as_usize();
+    }
+    if total != n + 1 {
+        Err(Error::IllegalArguments)?
+    }
+
+    // symbols are all counted once, so no count exceeding freq implies counts equal to freq
+    let mut occ = vec![0usize; freq.len()];
+    let mut lf = vec![I::default(); n + 1];
+    for (row, lf_row) in lf.iter_mut().enumerate() {
+        if n == 0 || row == primary {
+            continue;
+        }
+        let c = if row < primary { bwt[row] } else { bwt[row - 1] }.into();
+        if c >= freq.len() || occ[c] == freq[c].as_usize() {
+            Err(Error::IllegalArguments)?
+        }
+        *lf_row = I::from_usize(first[c] + occ[c]);
+        occ[c] += 1;
+    }
+    Ok(lf)
+}
+
+/// Recovers suffix array from bwt in libsais layout by LF-walking from the sentinel row.
+#[inline]
+pub fn sa_from_bwt<T: Copy + Into<usize>, I: SaIndex>(bwt: &[T], sa: &mut [I], primary: I) -> Result<(), Error<I>> {
+    let n = max_size(same_size(bwt.len(), sa.len())?, I::MAX_LENGTH - 1)?;
+    if n == 0 {
+        return Ok(());
+    }
+    if primary < I::from_usize(1) || primary.as_usize() > n {
+        Err(Error::IllegalArguments)?
+    }
+    let sigma = bwt.iter().map(|&c| c.into() + 1).max().unwrap_or(0);
+    let mut freq = vec![0usize; sigma];
+    for &c in bwt {
+        freq[c.into()] += 1;
+    }
+    let freq: Vec<I> = freq.into_iter().map(I::from_usize).collect();
+    let lf = lf_mapping(bwt, primary.as_usize(), &freq)?;
+
+    let mut row = 0;
+    for p in (0..n).rev() {
+        row = lf[row].as_usize();
+        sa[row - 1] = I::from_usize(p);
+    }
+    Ok(())
+}
+
+/// Rows of the suffix array, possibly sparse, whose suffixes start with `pattern`, found by binary search.
+#[inline]
+#[cfg(feature = "sais32")]
+pub fn pattern_range<T: Ord, I: SaIndex>(t: &[T], sa: &[I], pattern: &[T]) -> std::ops::Range<usize> {
+    let prefix = |p: I| {
+        let p = p.as_usize();
+        &t[p..Ord::min(p + pattern.len(), t.len())]
+    };
+    let start = sa.partition_point(|&p| prefix(p) < pattern);
+    let end = start + sa[start..].partition_point(|&p| prefix(p) == pattern);
+    start..end
+}
+
+/// Continues crc-32 (ieee 802.3) checksum `crc` of preceding data with `data`, starting from `0`.
+#[cfg(any(feature = "sais32", feature = "mmap"))]
+pub fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut c = i as u32;
+            let mut k = 0;
+            while k < 8 {
+                c = if c & 1 != 0 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 };
+                k += 1;
+            }
+            table[i] = c;
+            i += 1;
+        }
+        table
+    };
+
+    !data
+        .iter()
+        .fold(!crc, |crc, &b| TABLE[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8))
+}
+
+/// Reinterprets mapped bytes as elements, `None` if their length or alignment does not fit the element type.
+#[inline]
+#[cfg(feature = "mmap")]
+pub fn mapped_slice<E: crate::file::Element>(bytes: &[u8]) -> Option<&[E]> {
+    if !bytes.len().is_multiple_of(E::WIDTH) || !(bytes.as_ptr() as usize).is_multiple_of(std::mem::align_of::<E>()) {
+        return None;
+    }
+    // SAFETY: bytes are in bounds and aligned, elements are plain integers valid for any bits
+    Some(unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const E, bytes.len() / E::WIDTH) })
+}
+
+/// Reinterprets mapped bytes as mutable elements, `None` if their length or alignment does not fit the element type.
+#[inline]
+#[cfg(all(feature = "mmap", any(feature = "sais32", feature = "sais64")))]
+pub fn mapped_slice_mut<E: crate::file::Element>(bytes: &mut [u8]) -> Option<&mut [E]> {
+    if !bytes.len().is_multiple_of(E::WIDTH) || !(bytes.as_ptr() as usize).is_multiple_of(std::mem::align_of::<E>()) {
+        return None;
+    }
+    // SAFETY: bytes are in bounds and aligned, elements are plain integers valid for any bits
+    Some(unsafe { std::slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut E, bytes.len() / E::WIDTH) })
+}
@@ -0,0 +1,509 @@
+//! Block-sorting compressor in the style of bzip2, built on [`bwt`](crate::sais32::SaisContext::bwt)
+//! and [`unbwt`](crate::sais32::UnbwtContext::unbwt).
+//!
+//! Input is split into blocks, each of which is transformed by bwt, move-to-front and zero run-length coding,
+//! then entropy coded by a canonical huffman code. The container consists of a header, framed blocks carrying
+//! their sizes, primary indices and crc, and a trailer carrying crc of the whole input.
+//!
+//! All integers in the container are little endian:
+//!
+//! ```text
+//! header  := magic "SBZ1", block_size: u32
+//! block   := length: u32 (> 0), primary: u32, crc: u32, payload_length: u32, payload
+//! trailer := 0: u32, crc: u32
+//! payload := code lengths: [u8; 258], huffman coded symbols terminated by end-of-block symbol
+//! ```
+
+use std::io::{self, Read, Write};
+
+use crate::common::crc32_update;
+use crate::errors::Error;
+use crate::sais32::{Result, SaisContext, UnbwtContext, MAX_LENGTH};
+
+/// Magic bytes of the container header.
+pub const MAGIC: &[u8; 4] = b"SBZ1";
+
+/// Default block size, same as `bzip2 -9`.
+pub const DEFAULT_BLOCK_SIZE: usize = 900_000;
+
+/// Maximum block size supported by the container.
+pub const MAX_BLOCK_SIZE: usize = MAX_LENGTH;
+
+const RUN_A: usize = 0;
+const RUN_B: usize = 1;
+const END_OF_BLOCK: usize = 257;
+const ALPHABET_SIZE: usize = 258;
+const MAX_CODE_LENGTH: u8 = 20;
+
+/// Compresses `data` with blocks of `block_size` bytes.
+///
+/// Returns `Err(Error::IllegalArguments)` if `block_size` is zero or larger than `MAX_BLOCK_SIZE`.
+///
+/// # Examples
+///
+/// ```
+/// use sais::compress::{compress, decompress, DEFAULT_BLOCK_SIZE};
+///
+/// let data = b"how much wood would a woodchuck chuck if a woodchuck could chuck wood".repeat(10);
+/// let compressed = compress(&data, DEFAULT_BLOCK_SIZE).unwrap();
+/// assert!(compressed.len() < data.len());
+/// assert_eq!(decompress(&compressed).unwrap(), data);
+/// ```
+pub fn compress(data: &[u8], block_size: usize) -> Result<Vec<u8>> {
+    let mut encoder = BlockEncoder::new(block_size)?;
+    let mut output = Vec::with_capacity(data.len() / 2 + 16);
+    write_header(&mut output, MAGIC, block_size).map_err(format_error)?;
+    for block in data.chunks(block_size) {
+        encoder.encode(block, &mut output)?;
+    }
+    write_trailer(&mut output, crc32(data));
+    Ok(output)
+}
+
+/// Decompresses the output of [`compress`].
+///
+/// Returns `Err(Error::IllegalArguments)` if `data` is truncated, corrupted or fails crc check.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+    let mut input = data;
+    let block_size = read_header(&mut input, MAGIC).map_err(format_error)?;
+    let mut decoder = BlockDecoder::new(block_size)?;
+    let mut output = Vec::new();
+    while let Some(frame) = read_frame(&mut input, block_size)? {
+        decoder.decode(&frame, &mut output)?;
+    }
+    let crc = read_u32(&mut input).map_err(format_error)?;
+    if !input.is_empty() || crc != crc32(&output) {
+        Err(Error::IllegalArguments)?
+    }
+    Ok(output)
+}
+
+/// Header of a compressed block.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) struct Frame<'a> {
+    pub length: usize,
+    pub primary: i32,
+    pub crc: u32,
+    pub payload: &'a [u8],
+}
+
+pub(crate) fn write_trailer(output: &mut Vec<u8>, crc: u32) {
+    output.extend_from_slice(&0u32.to_le_bytes());
+    output.extend_from_slice(&crc.to_le_bytes());
+}
+
+/// Reads next block frame, returns `None` at the trailer.
+pub(crate) fn read_frame<'a>(input: &mut &'a [u8], block_size: usize) -> Result<Option<Frame<'a>>> {
+    let Some((length, primary)) = read_frame_start(input, block_size).map_err(format_error)? else {
+        return Ok(None);
+    };
+    let crc = read_u32(input).map_err(format_error)?;
+    let payload_length = read_u32(input).map_err(format_error)? as usize;
+    let payload = read_bytes(input, payload_length)?;
+    Ok(Some(Frame { length, primary, crc, payload }))
+}
+
+fn read_bytes<'a>(input: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    if input.len() < n {
+        Err(Error::IllegalArguments)?
+    }
+    let (bytes, rest) = input.split_at(n);
+    *input = rest;
+    Ok(bytes)
+}
+
+/// Truncated or malformed container.
+fn format_error(_: io::Error) -> Error<i32> {
+    Error::IllegalArguments
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Writes header of `magic` and `block_size`, which is shared with the [`stream`](crate::stream) format.
+pub(crate) fn write_header<W: Write>(output: &mut W, magic: &[u8; 4], block_size: usize) -> io::Result<()> {
+    output.write_all(magic)?;
+    write_u32(output, block_size as u32)
+}
+
+/// Reads header of `magic`, returns its block size.
+pub(crate) fn read_header<R: Read>(input: &mut R, magic: &[u8; 4]) -> io::Result<usize> {
+    let mut bytes = [0u8; 4];
+    input.read_exact(&mut bytes)?;
+    if &bytes != magic {
+        Err(invalid_data("bad magic bytes"))?
+    }
+    let block_size = read_u32(input)? as usize;
+    if block_size == 0 || block_size > MAX_BLOCK_SIZE {
+        Err(invalid_data("bad block size"))?
+    }
+    Ok(block_size)
+}
+
+/// Writes length and primary index, which start every block frame.
+pub(crate) fn write_frame_start<W: Write>(output: &mut W, length: usize, primary: i32) -> io::Result<()> {
+    write_u32(output, length as u32)?;
+    write_u32(output, primary as u32)
+}
+
+/// Reads length and primary index of next block frame, returns `None` at the trailer.
+pub(crate) fn read_frame_start<R: Read>(input: &mut R, block_size: usize) -> io::Result<Option<(usize, i32)>> {
+    let length = read_u32(input)? as usize;
+    if length == 0 {
+        return Ok(None);
+    }
+    if length > block_size {
+        Err(invalid_data("block is larger than block size"))?
+    }
+    let primary = read_u32(input)? as usize;
+    if primary < 1 || primary > length {
+        Err(invalid_data("bad primary index"))?
+    }
+    Ok(Some((length, primary as i32)))
+}
+
+pub(crate) fn write_u32<W: Write>(output: &mut W, value: u32) -> io::Result<()> {
+    output.write_all(&value.to_le_bytes())
+}
+
+pub(crate) fn read_u32<R: Read>(input: &mut R) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    input.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Encoder of single blocks, reusing its sais context and buffers.
+pub(crate) struct BlockEncoder {
+    ctx: SaisContext,
+    bwt: Vec<u8>,
+    tmp: Vec<i32>,
+    symbols: Vec<u16>,
+}
+
+impl BlockEncoder {
+    pub fn new(block_size: usize) -> Result<Self> {
+        if block_size == 0 || block_size > MAX_BLOCK_SIZE {
+            Err(Error::IllegalArguments)?
+        }
+        let ctx = SaisContext::new().ok_or(Error::InternalError)?;
+        Ok(BlockEncoder {
+            ctx,
+            bwt: Vec::new(),
+            tmp: Vec::new(),
+            symbols: Vec::new(),
+        })
+    }
+
+    /// Appends framed compressed `block` to `output`.
+    pub fn encode(&mut self, block: &[u8], output: &mut Vec<u8>) -> Result<()> {
+        let n = block.len();
+        self.bwt.resize(n, 0);
+        self.tmp.resize(n, 0);
+        let primary = self.ctx.bwt(block, &mut self.bwt, &mut self.tmp, None)?;
+
+        mtf_rle_encode(&self.bwt, &mut self.symbols);
+        let mut freq = [0usize; ALPHABET_SIZE];
+        for &s in self.symbols.iter() {
+            freq[s as usize] += 1;
+        }
+        let lengths = code_lengths(&freq);
+        let codes = canonical_codes(&lengths);
+
+        write_frame_start(output, n, primary).map_err(format_error)?;
+        output.extend_from_slice(&crc32(block).to_le_bytes());
+        let payload_length_at = output.len();
+        output.extend_from_slice(&0u32.to_le_bytes());
+        let payload_at = output.len();
+        output.extend_from_slice(&lengths);
+        let mut writer = BitWriter::new(output);
+        for &s in self.symbols.iter() {
+            writer.write(codes[s as usize], lengths[s as usize]);
+        }
+        writer.finish();
+        let payload_length = (output.len() - payload_at) as u32;
+        output[payload_length_at..payload_at].copy_from_slice(&payload_length.to_le_bytes());
+        Ok(())
+    }
+}
+
+/// Decoder of single blocks, reusing its unbwt context and buffers.
+pub(crate) struct BlockDecoder {
+    ctx: UnbwtContext,
+    bwt: Vec<u8>,
+    tmp: Vec<i32>,
+    block_size: usize,
+}
+
+impl BlockDecoder {
+    pub fn new(block_size: usize) -> Result<Self> {
+        let ctx = UnbwtContext::new().ok_or(Error::InternalError)?;
+        Ok(BlockDecoder {
+            ctx,
+            bwt: Vec::new(),
+            tmp: Vec::new(),
+            block_size,
+        })
+    }
+
+    /// Appends decompressed block of `frame` to `output`.
+    pub fn decode(&mut self, frame: &Frame, output: &mut Vec<u8>) -> Result<()> {
+        let n = frame.length;
+        if n == 0 || n > self.block_size || frame.primary < 1 || frame.primary as usize > n {
+            Err(Error::IllegalArguments)?
+        }
+        let mut payload = frame.payload;
+        let lengths = read_bytes(&mut payload, ALPHABET_SIZE)?;
+        let table = DecodeTable::new(lengths)?;
+        mtf_rle_decode(&table, &mut BitReader::new(payload), n, &mut self.bwt)?;
+
+        let mut freq = [0i32; 256];
+        for &c in self.bwt.iter() {
+            freq[c as usize] += 1;
+        }
+        let start = output.len();
+        output.resize(start + n, 0);
+        self.tmp.resize(n + 1, 0);
+        self.ctx
+            .unbwt(&self.bwt, &mut output[start..], &mut self.tmp, Some(&freq), frame.primary)?;
+        if crc32(&output[start..]) != frame.crc {
+            output.truncate(start);
+            Err(Error::IllegalArguments)?
+        }
+        Ok(())
+    }
+}
+
+fn mtf_rle_encode(u: &[u8], symbols: &mut Vec<u16>) {
+    fn flush_run(run: &mut usize, symbols: &mut Vec<u16>) {
+        // bijective base-2 with digits RUN_A = 1 and RUN_B = 2
+        while *run > 0 {
+            *run -= 1;
+            symbols.push(if (*run).is_multiple_of(2) { RUN_A } else { RUN_B } as u16);
+            *run /= 2;
+        }
+    }
+
+    symbols.clear();
+    let mut order: [u8; 256] = std::array::from_fn(|c| c as u8);
+    let mut run = 0;
+    for &c in u {
+        let rank = order.iter().position(|&x| x == c).unwrap_or_default();
+        order.copy_within(0..rank, 1);
+        order[0] = c;
+        if rank == 0 {
+            run += 1;
+        } else {
+            flush_run(&mut run, symbols);
+            symbols.push(rank as u16 + 1);
+        }
+    }
+    flush_run(&mut run, symbols);
+    symbols.push(END_OF_BLOCK as u16);
+}
+
+fn mtf_rle_decode(table: &DecodeTable, reader: &mut BitReader, n: usize, u: &mut Vec<u8>) -> Result<()> {
+    u.clear();
+    let mut order: [u8; 256] = std::array::from_fn(|c| c as u8);
+    let (mut run, mut digit) = (0usize, 1usize);
+    loop {
+        let s = table.decode(reader)?;
+        if s == RUN_A || s == RUN_B {
+            run += digit << s;
+            digit = digit.checked_mul(2).ok_or(Error::IllegalArguments)?;
+            if run > n {
+                Err(Error::IllegalArguments)?
+            }
+            continue;
+        }
+        if u.len() + run > n {
+            Err(Error::IllegalArguments)?
+        }
+        u.resize(u.len() + run, order[0]);
+        (run, digit) = (0, 1);
+        if s == END_OF_BLOCK {
+            break;
+        }
+        let rank = s - 1;
+        let c = order[rank];
+        order.copy_within(0..rank, 1);
+        order[0] = c;
+        if u.len() == n {
+            Err(Error::IllegalArguments)?
+        }
+        u.push(c);
+    }
+    if u.len() != n {
+        Err(Error::IllegalArguments)?
+    }
+    Ok(())
+}
+
+/// Computes huffman code lengths limited to `MAX_CODE_LENGTH`, zero for absent symbols.
+fn code_lengths(freq: &[usize; ALPHABET_SIZE]) -> [u8; ALPHABET_SIZE] {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    let mut lengths = [0u8; ALPHABET_SIZE];
+    let present: Vec<usize> = (0..ALPHABET_SIZE).filter(|&s| freq[s] > 0).collect();
+    if present.len() == 1 {
+        lengths[present[0]] = 1;
+        return lengths;
+    }
+    let mut weights: Vec<usize> = present.iter().map(|&s| freq[s]).collect();
+    loop {
+        // leaves are 0..present.len(), internal nodes follow
+        let mut parent = vec![0usize; 2 * present.len()];
+        let mut heap: BinaryHeap<_> = weights
+            .iter()
+            .enumerate()
+            .map(|(i, &w)| Reverse((w, i)))
+            .collect();
+        let mut next = present.len();
+        while heap.len() > 1 {
+            let Reverse((w1, i1)) = heap.pop().unwrap_or_default();
+            let Reverse((w2, i2)) = heap.pop().unwrap_or_default();
+            parent[i1] = next;
+            parent[i2] = next;
+            heap.push(Reverse((w1 + w2, next)));
+            next += 1;
+        }
+        let root = next - 1;
+        let mut depth = vec![0u8; next];
+        for node in (0..root).rev() {
+            depth[node] = depth[parent[node]].saturating_add(1);
+        }
+        if depth[..present.len()].iter().all(|&d| d <= MAX_CODE_LENGTH) {
+            for (i, &s) in present.iter().enumerate() {
+                lengths[s] = depth[i];
+            }
+            return lengths;
+        }
+        for w in weights.iter_mut() {
+            *w = *w / 2 + 1;
+        }
+    }
+}
+
+/// Assigns canonical codes in order of (length, symbol).
+fn canonical_codes(lengths: &[u8; ALPHABET_SIZE]) -> [u32; ALPHABET_SIZE] {
+    let mut codes = [0u32; ALPHABET_SIZE];
+    let mut code = 0u32;
+    for length in 1..=MAX_CODE_LENGTH {
+        for s in 0..ALPHABET_SIZE {
+            if lengths[s] == length {
+                codes[s] = code;
+                code += 1;
+            }
+        }
+        code <<= 1;
+    }
+    codes
+}
+
+/// Canonical huffman decoding table.
+struct DecodeTable {
+    counts: [u32; MAX_CODE_LENGTH as usize + 1],
+    symbols: Vec<u16>,
+}
+
+impl DecodeTable {
+    fn new(lengths: &[u8]) -> Result<Self> {
+        let mut counts = [0u32; MAX_CODE_LENGTH as usize + 1];
+        for &length in lengths {
+            if length > MAX_CODE_LENGTH {
+                Err(Error::IllegalArguments)?
+            }
+            counts[length as usize] += 1;
+        }
+        // reject oversubscribed codes
+        let mut left = 1i64;
+        for &count in counts.iter().skip(1) {
+            left = 2 * left - count as i64;
+            if left < 0 {
+                Err(Error::IllegalArguments)?
+            }
+        }
+        let mut symbols = Vec::with_capacity(lengths.len());
+        for length in 1..=MAX_CODE_LENGTH {
+            symbols.extend(
+                (0..lengths.len())
+                    .filter(|&s| lengths[s] == length)
+                    .map(|s| s as u16),
+            );
+        }
+        counts[0] = 0;
+        Ok(DecodeTable { counts, symbols })
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<usize> {
+        let (mut code, mut first, mut index) = (0i64, 0i64, 0i64);
+        for &count in self.counts.iter().skip(1) {
+            code |= reader.read()? as i64;
+            let count = count as i64;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize] as usize);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(Error::IllegalArguments)
+    }
+}
+
+/// Msb-first bit writer.
+struct BitWriter<'a> {
+    output: &'a mut Vec<u8>,
+    buffer: u64,
+    bits: u32,
+}
+
+impl<'a> BitWriter<'a> {
+    fn new(output: &'a mut Vec<u8>) -> Self {
+        BitWriter { output, buffer: 0, bits: 0 }
+    }
+
+    fn write(&mut self, code: u32, length: u8) {
+        self.buffer = (self.buffer << length) | code as u64;
+        self.bits += length as u32;
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.output.push((self.buffer >> self.bits) as u8);
+        }
+    }
+
+    fn finish(mut self) {
+        if self.bits > 0 {
+            let padding = 8 - self.bits as u8;
+            self.write(0, padding);
+        }
+    }
+}
+
+/// Msb-first bit reader.
+struct BitReader<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(input: &'a [u8]) -> Self {
+        BitReader { input, position: 0 }
+    }
+
+    fn read(&mut self) -> Result<u8> {
+        let byte = self
+            .input
+            .get(self.position / 8)
+            .ok_or(Error::IllegalArguments)?;
+        let bit = (byte >> (7 - self.position % 8)) & 1;
+        self.position += 1;
+        Ok(bit)
+    }
+}
+
+/// Crc-32 (ieee 802.3) checksum.
+pub fn crc32(data: &[u8]) -> u32 {
+    crc32_update(0, data)
+}
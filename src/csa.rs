@@ -0,0 +1,441 @@
+//! Compressed suffix array based on the Ψ function, with sampled suffix array and inverse suffix array.
+//!
+//! With the sentinel suffix as row `0`, `Ψ[i]` is the row of the suffix one position after the suffix of row `i`.
+//! Ψ is increasing within rows of suffixes starting with the same symbol, so it is stored as variable length
+//! deltas with absolute samples every `PSI_BLOCK` rows. Suffix array values are sampled at text positions
+//! divisible by the sample rate and recovered by following Ψ, text symbols are read off the first column.
+
+use std::ops::Range;
+
+use crate::common::{inverse_suffix_array, max_size, same_size};
+use crate::errors::Error;
+use crate::index::SaIndex;
+
+/// Number of rows per absolute Ψ sample.
+const PSI_BLOCK: usize = 32;
+
+/// Bit vector with constant time rank, serialized as its words, from which ranks are rebuilt on load.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "Vec<u64>", into = "Vec<u64>"))]
+struct RankBits {
+    words: Vec<u64>,
+    ranks: Vec<usize>,
+}
+
+impl From<RankBits> for Vec<u64> {
+    fn from(bits: RankBits) -> Self {
+        bits.words
+    }
+}
+
+impl From<Vec<u64>> for RankBits {
+    fn from(words: Vec<u64>) -> Self {
+        let mut ranks = Vec::with_capacity(words.len());
+        let mut rank = 0;
+        for word in words.iter() {
+            ranks.push(rank);
+            rank += word.count_ones() as usize;
+        }
+        RankBits { words, ranks }
+    }
+}
+
+impl RankBits {
+    fn new(bits: impl ExactSizeIterator<Item = bool>) -> Self {
+        let mut words = vec![0u64; bits.len().div_ceil(64)];
+        for (i, bit) in bits.enumerate() {
+            words[i / 64] |= (bit as u64) << (i % 64);
+        }
+        Self::from(words)
+    }
+
+    #[inline]
+    fn get(&self, i: usize) -> bool {
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    /// Number of set bits before `i`.
+    #[inline]
+    fn rank(&self, i: usize) -> usize {
+        let below = self.words[i / 64] & ((1u64 << (i % 64)) - 1);
+        self.ranks[i / 64] + below.count_ones() as usize
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        self.words.len() * 8 + self.ranks.len() * std::mem::size_of::<usize>()
+    }
+}
+
+/// Compressed suffix array of a `u8` text.
+///
+/// Deserialization checks in linear time that the fields form a compressed suffix array of some text.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "CompressedSuffixArrayParts<I>", bound(deserialize = "I: SaIndex + serde::Deserialize<'de>"))
+)]
+pub struct CompressedSuffixArray<I> {
+    n: usize,
+    rate: usize,
+    first: Vec<usize>,
+    psi_samples: Vec<I>,
+    psi_offsets: Vec<usize>,
+    psi_deltas: Vec<u8>,
+    sampled: RankBits,
+    sa_samples: Vec<I>,
+    isa_samples: Vec<I>,
+}
+
+/// Fields of a deserialized compressed suffix array, before validation.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CompressedSuffixArrayParts<I> {
+    n: usize,
+    rate: usize,
+    first: Vec<usize>,
+    psi_samples: Vec<I>,
+    psi_offsets: Vec<usize>,
+    psi_deltas: Vec<u8>,
+    sampled: RankBits,
+    sa_samples: Vec<I>,
+    isa_samples: Vec<I>,
+}
+
+#[cfg(feature = "serde")]
+impl<I: SaIndex> TryFrom<CompressedSuffixArrayParts<I>> for CompressedSuffixArray<I> {
+    type Error = &'static str;
+
+    fn try_from(parts: CompressedSuffixArrayParts<I>) -> Result<Self, Self::Error> {
+        let csa = CompressedSuffixArray {
+            n: parts.n,
+            rate: parts.rate,
+            first: parts.first,
+            psi_samples: parts.psi_samples,
+            psi_offsets: parts.psi_offsets,
+            psi_deltas: parts.psi_deltas,
+            sampled: parts.sampled,
+            sa_samples: parts.sa_samples,
+            isa_samples: parts.isa_samples,
+        };
+        match csa.is_valid() {
+            true => Ok(csa),
+            false => Err("inconsistent compressed suffix array"),
+        }
+    }
+}
+
+impl<I: SaIndex> CompressedSuffixArray<I> {
+    /// Constructs compressed suffix array from text and its suffix array.
+    ///
+    /// Returns `Err(Error::IllegalArguments)` if `t` and `sa` differ in length, `sa` is not a permutation,
+    /// or `sample_rate` is zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - Input text.
+    /// * `sa` - Suffix array of `t` without free space, e.g. output of `sais`.
+    /// * `sample_rate` - Sampling rate of suffix array and inverse suffix array values, trading space for time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sais::csa::CompressedSuffixArray;
+    /// use sais::sais32::sais;
+    ///
+    /// let t = b"mississippi";
+    /// let mut sa = vec![0; t.len()];
+    /// sais(t, &mut sa, None).unwrap();
+    ///
+    /// let csa = CompressedSuffixArray::new(t, &sa, 4).unwrap();
+    /// assert_eq!(csa.lookup(3), 1);
+    /// assert_eq!(csa.inverse(4), 2);
+    /// assert_eq!(csa.extract(2..6), b"ssis");
+    /// ```
+    pub fn new(t: &[u8], sa: &[I], sample_rate: usize) -> Result<Self, Error<I>> {
+        let n = max_size(same_size(t.len(), sa.len())?, I::MAX_LENGTH - 1)?;
+        if sample_rate == 0 {
+            Err(Error::IllegalArguments)?
+        }
+        let isa = inverse_suffix_array(sa)?;
+
+        // row of suffix p, the sentinel suffix n is row 0
+        let row = |p: usize| if p == n { 0 } else { isa[p].as_usize() + 1 };
+        let suffix = |r: usize| if r == 0 { n } else { sa[r - 1].as_usize() };
+
+        let mut first = vec![0usize; 257];
+        for &c in t {
+            first[c as usize + 1] += 1;
+        }
+        first[0] = 1;
+        for c in 0..256 {
+            first[c + 1] += first[c];
+        }
+
+        let mut psi_samples = Vec::with_capacity((n + 1).div_ceil(PSI_BLOCK));
+        let mut psi_offsets = Vec::with_capacity((n + 1).div_ceil(PSI_BLOCK));
+        let mut psi_deltas = Vec::new();
+        let mut previous = 0;
+        for r in 0..=n {
+            let psi = row((suffix(r) + 1) % (n + 1));
+            if r % PSI_BLOCK == 0 {
+                psi_samples.push(I::from_usize(psi));
+                psi_offsets.push(psi_deltas.len());
+            } else {
+                write_varint(&mut psi_deltas, zigzag(psi as i64 - previous as i64));
+            }
+            previous = psi;
+        }
+        psi_deltas.shrink_to_fit();
+
+        let sampled = RankBits::new((0..n + 1).map(|r| suffix(r) % sample_rate == 0));
+        let sa_samples = (0..=n)
+            .map(suffix)
+            .filter(|p| p % sample_rate == 0)
+            .map(|p| I::from_usize(p / sample_rate))
+            .collect();
+        let isa_samples = (0..=n)
+            .step_by(sample_rate)
+            .map(|p| I::from_usize(row(p)))
+            .collect();
+        Ok(CompressedSuffixArray {
+            n,
+            rate: sample_rate,
+            first,
+            psi_samples,
+            psi_offsets,
+            psi_deltas,
+            sampled,
+            sa_samples,
+            isa_samples,
+        })
+    }
+
+    /// Length of the text.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Whether the text is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Sampling rate of suffix array and inverse suffix array values.
+    #[inline]
+    pub fn sample_rate(&self) -> usize {
+        self.rate
+    }
+
+    /// Approximate heap size of the compressed representation.
+    pub fn size_in_bytes(&self) -> usize {
+        let index = std::mem::size_of::<I>();
+        self.first.len() * std::mem::size_of::<usize>()
+            + (self.psi_samples.len() + self.sa_samples.len() + self.isa_samples.len()) * index
+            + self.psi_offsets.len() * std::mem::size_of::<usize>()
+            + self.psi_deltas.len()
+            + self.sampled.size_in_bytes()
+    }
+
+    /// Whether the fields form a compressed suffix array of some text, i.e. Ψ is increasing within buckets
+    /// of the first column and a single cycle through all rows, and samples agree with positions on the cycle.
+    #[cfg(feature = "serde")]
+    fn is_valid(&self) -> bool {
+        let n = self.n;
+        let blocks = (n + 1).div_ceil(PSI_BLOCK);
+        if n >= I::MAX_LENGTH
+            || self.rate == 0
+            || self.first.len() != 257
+            || self.first[0] != 1
+            || self.first[256] != n + 1
+            || self.first.windows(2).any(|pair| pair[0] > pair[1])
+            || self.psi_samples.len() != blocks
+            || self.psi_offsets.len() != blocks
+            || self.sampled.words.len() != (n + 1).div_ceil(64)
+            || self.sa_samples.len() != n / self.rate + 1
+            || self.isa_samples.len() != n / self.rate + 1
+            || self
+                .isa_samples
+                .iter()
+                .any(|&r| r < I::default() || r.as_usize() > n)
+        {
+            return false;
+        }
+
+        let (mut offset, mut previous) = (0, 0);
+        for r in 0..=n {
+            let psi = if r % PSI_BLOCK == 0 {
+                let sample = self.psi_samples[r / PSI_BLOCK];
+                if self.psi_offsets[r / PSI_BLOCK] != offset || sample < I::default() || sample.as_usize() > n {
+                    return false;
+                }
+                sample.as_usize()
+            } else {
+                let (delta, length) = read_varint(&self.psi_deltas[offset..]);
+                offset += length;
+                match (previous as i64).checked_add(unzigzag(delta)) {
+                    Some(psi) if length > 0 && (0..=n as i64).contains(&psi) => psi as usize,
+                    _ => return false,
+                }
+            };
+            if r > 1 && self.row_symbol(r - 1) == self.row_symbol(r) && psi <= previous {
+                return false;
+            }
+            previous = psi;
+        }
+        if offset != self.psi_deltas.len() {
+            return false;
+        }
+
+        // the cycle from the sentinel row visits rows of suffixes 0, 1, ..., n in text order
+        let mut visited = vec![false; n + 1];
+        let mut r = 0;
+        for p in 0..=n {
+            r = self.row_psi(r);
+            if visited[r] || (r == 0) != (p == n) {
+                return false;
+            }
+            visited[r] = true;
+            let sampled = p % self.rate == 0;
+            if self.sampled.get(r) != sampled
+                || sampled
+                    && (self.sa_samples.get(self.sampled.rank(r)) != Some(&I::from_usize(p / self.rate)) || self.isa_samples[p / self.rate].as_usize() != r)
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Ψ of row `r`, including the sentinel row `0`.
+    fn row_psi(&self, r: usize) -> usize {
+        let block = r / PSI_BLOCK;
+        let mut psi = self.psi_samples[block].as_usize() as i64;
+        let mut offset = self.psi_offsets[block];
+        for _ in block * PSI_BLOCK..r {
+            let (delta, length) = read_varint(&self.psi_deltas[offset..]);
+            psi += unzigzag(delta);
+            offset += length;
+        }
+        psi as usize
+    }
+
+    /// First symbol of the suffix of row `r`, which must not be the sentinel row `0`.
+    #[inline]
+    fn row_symbol(&self, r: usize) -> u8 {
+        (self.first.partition_point(|&f| f <= r) - 1) as u8
+    }
+
+    /// Suffix array value `sa[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= len()`.
+    pub fn lookup(&self, i: usize) -> usize {
+        assert!(i < self.n, "index {} out of bounds {}", i, self.n);
+        let mut r = i + 1;
+        let mut steps = 0;
+        while !self.sampled.get(r) {
+            r = self.row_psi(r);
+            steps += 1;
+        }
+        let p = self.sa_samples[self.sampled.rank(r)].as_usize() * self.rate;
+        // the sentinel suffix n is sampled as row 0, which is reachable only by wrapping around
+        (p + self.n + 1 - steps) % (self.n + 1)
+    }
+
+    /// Row of the suffix at `j` in the suffix array of the sentinel-included text.
+    fn row(&self, j: usize) -> usize {
+        let mut r = self.isa_samples[j / self.rate].as_usize();
+        for _ in 0..j % self.rate {
+            r = self.row_psi(r);
+        }
+        r
+    }
+
+    /// Inverse suffix array value `isa[j]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `j >= len()`.
+    pub fn inverse(&self, j: usize) -> usize {
+        assert!(j < self.n, "position {} out of bounds {}", j, self.n);
+        self.row(j) - 1
+    }
+
+    /// Ψ of rank `i`, i.e. the rank of the suffix one position after `sa[i]`, or `None` for the last suffix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= len()`.
+    #[inline]
+    pub fn psi(&self, i: usize) -> Option<usize> {
+        assert!(i < self.n, "index {} out of bounds {}", i, self.n);
+        self.row_psi(i + 1).checked_sub(1)
+    }
+
+    /// Text symbol at position `p`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p >= len()`.
+    #[inline]
+    pub fn symbol(&self, p: usize) -> u8 {
+        assert!(p < self.n, "position {} out of bounds {}", p, self.n);
+        self.row_symbol(self.row(p))
+    }
+
+    /// Extracts text range `[start, end)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of text bounds.
+    pub fn extract(&self, range: Range<usize>) -> Vec<u8> {
+        assert!(range.start <= range.end && range.end <= self.n, "range {:?} out of text bounds {}", range, self.n);
+        let mut out = Vec::with_capacity(range.len());
+        if range.is_empty() {
+            return out;
+        }
+        let mut r = self.row(range.start);
+        for _ in range {
+            out.push(self.row_symbol(r));
+            r = self.row_psi(r);
+        }
+        out
+    }
+}
+
+#[inline]
+fn zigzag(x: i64) -> u64 {
+    ((x << 1) ^ (x >> 63)) as u64
+}
+
+#[inline]
+fn unzigzag(x: u64) -> i64 {
+    (x >> 1) as i64 ^ -((x & 1) as i64)
+}
+
+#[inline]
+fn write_varint(out: &mut Vec<u8>, mut x: u64) {
+    while x >= 0x80 {
+        out.push(x as u8 | 0x80);
+        x >>= 7;
+    }
+    out.push(x as u8);
+}
+
+#[inline]
+fn read_varint(bytes: &[u8]) -> (u64, usize) {
+    let mut x = 0u64;
+    // at most 10 bytes of 7 bits fit in u64
+    for (k, &b) in bytes.iter().take(10).enumerate() {
+        x |= ((b & 0x7f) as u64) << (7 * k);
+        if b < 0x80 {
+            return (x, k + 1);
+        }
+    }
+    (x, bytes.len().min(10))
+}
@@ -0,0 +1,270 @@
+//! Compressed suffix tree, i.e. suffix tree navigation simulated on top of compressed suffix array and lcp array.
+//!
+//! A node is an lcp-interval `[i, j)` of suffix array ranks together with its string depth, leaves being
+//! singleton ranges whose depth is the length of the suffix. Following Fischer, Mäkinen and Navarro,
+//! parents and lowest common ancestors are found by previous/next smaller values of the lcp array,
+//! children by range minimum queries, and suffix links by Ψ of the [`CompressedSuffixArray`].
+//!
+//! Both the compressed suffix array and the lcp array are borrowed, and the tree itself only owns minima of
+//! lcp blocks, about `2n / 64` index values, which answer range minimum and smaller value queries in
+//! `O(64 + log n)` time. The lcp array is kept plain, so that it takes `n` index values, e.g. memory-mapped,
+//! and suffix array values and text symbols cost `O(sample_rate)` steps of Ψ each.
+//!
+//! The text has no explicit sentinel, so a suffix that is a prefix of another suffix is a leaf with the same
+//! string depth as its parent, standing for the implicit sentinel edge.
+
+use std::ops::Range;
+
+use crate::common::same_size;
+use crate::csa::CompressedSuffixArray;
+use crate::errors::Error;
+use crate::index::SaIndex;
+use crate::rmq::BlockRangeMinimum;
+
+/// Node of the simulated suffix tree.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node {
+    /// Length of the path label, i.e. the longest common prefix of all suffixes in the node.
+    pub depth: usize,
+    /// Range of suffix array ranks, i.e. leaves below the node.
+    pub range: Range<usize>,
+}
+
+/// Suffix tree of a `u8` text, simulated on its compressed suffix array and lcp array.
+pub struct CompressedSuffixTree<'a, I: SaIndex> {
+    csa: &'a CompressedSuffixArray<I>,
+    lcp: &'a [I],
+    rmq: BlockRangeMinimum<'a, I>,
+}
+
+impl<'a, I: SaIndex> CompressedSuffixTree<'a, I> {
+    /// Builds suffix tree navigation structures from compressed suffix array and lcp array.
+    ///
+    /// Returns `Err(Error::IllegalArguments)` if `csa` and `lcp` differ in length, or `lcp` contains negative values.
+    ///
+    /// # Arguments
+    ///
+    /// * `csa` - Compressed suffix array of the text.
+    /// * `lcp` - Lcp array of the text, exactly `text.len()` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sais::csa::CompressedSuffixArray;
+    /// use sais::cst::CompressedSuffixTree;
+    /// use sais::sais32::{lcp, plcp, sais};
+    ///
+    /// let t = b"mississippi";
+    /// let mut sa = vec![0; t.len()];
+    /// let mut plcp_array = vec![0; t.len()];
+    /// let mut lcp_array = vec![0; t.len()];
+    /// sais(t, &mut sa, None).unwrap();
+    /// plcp(t, &sa, &mut plcp_array).unwrap();
+    /// lcp(&plcp_array, &sa, &mut lcp_array).unwrap();
+    /// let csa = CompressedSuffixArray::new(t, &sa, 4).unwrap();
+    ///
+    /// let cst = CompressedSuffixTree::new(&csa, &lcp_array).unwrap();
+    /// let issi = cst.child(&cst.child(&cst.root(), b'i').unwrap(), b's').unwrap();
+    /// assert_eq!(cst.label(&issi), b"issi");
+    /// assert_eq!(cst.label(&cst.suffix_link(&issi).unwrap()), b"ssi");
+    /// assert_eq!(cst.parent(&issi), cst.child(&cst.root(), b'i'));
+    /// ```
+    pub fn new(csa: &'a CompressedSuffixArray<I>, lcp: &'a [I]) -> Result<Self, Error<I>> {
+        same_size(csa.len(), lcp.len())?;
+        if lcp.iter().any(|&l| l < I::default()) {
+            Err(Error::IllegalArguments)?
+        }
+        let rmq = BlockRangeMinimum::new(lcp);
+        Ok(CompressedSuffixTree { csa, lcp, rmq })
+    }
+
+    /// Length of the text.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lcp.len()
+    }
+
+    /// Whether the text is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lcp.is_empty()
+    }
+
+    /// Root node, with depth `0` covering all suffixes.
+    #[inline]
+    pub fn root(&self) -> Node {
+        Node {
+            depth: 0,
+            range: 0..self.len(),
+        }
+    }
+
+    /// Leaf of the suffix starting at text position `p`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p >= len()`.
+    #[inline]
+    pub fn leaf(&self, p: usize) -> Node {
+        let rank = self.csa.inverse(p);
+        Node {
+            depth: self.len() - p,
+            range: rank..rank + 1,
+        }
+    }
+
+    #[inline]
+    fn leaf_at(&self, rank: usize) -> Node {
+        Node {
+            depth: self.len() - self.csa.lookup(rank),
+            range: rank..rank + 1,
+        }
+    }
+
+    /// Whether `v` is the root.
+    #[inline]
+    pub fn is_root(&self, v: &Node) -> bool {
+        v.depth == 0 && v.range == (0..self.len())
+    }
+
+    /// Whether `v` is a leaf.
+    #[inline]
+    pub fn is_leaf(&self, v: &Node) -> bool {
+        v.range.len() == 1 && v.depth == self.len() - self.csa.lookup(v.range.start)
+    }
+
+    /// Path label of `v`, extracted from the compressed suffix array.
+    pub fn label(&self, v: &Node) -> Vec<u8> {
+        match v.range.len() {
+            0 => Vec::new(),
+            _ => {
+                let p = self.csa.lookup(v.range.start);
+                self.csa.extract(p..p + v.depth)
+            }
+        }
+    }
+
+    /// Text positions of suffixes below `v`, looked up in the compressed suffix array.
+    pub fn suffixes(&self, v: &Node) -> impl Iterator<Item = usize> + '_ {
+        v.range.clone().map(|rank| self.csa.lookup(rank))
+    }
+
+    /// Node of a range of ranks that is a child interval, i.e. with the depth of its own lcp-interval.
+    fn node_of(&self, range: Range<usize>) -> Node {
+        match range.len() {
+            1 => self.leaf_at(range.start),
+            _ => Node {
+                depth: self.rmq.min(range.start + 1..range.end).as_usize(),
+                range,
+            },
+        }
+    }
+
+    /// Node with the depth of extended lcp value at boundary `k`, spanning its smaller values on both sides.
+    fn enclosing(&self, k: usize) -> Node {
+        match boundary(self.lcp, k) {
+            0 => self.root(),
+            _ => {
+                let value = self.lcp[k];
+                // boundaries 0 and n are smaller than any other
+                let psv = self.rmq.find_last(1..k, |l| l < value).unwrap_or(0);
+                let nsv = self.rmq.find_first(k + 1..self.len(), |l| l < value);
+                Node {
+                    depth: value.as_usize(),
+                    range: psv..nsv.unwrap_or(self.len()),
+                }
+            }
+        }
+    }
+
+    /// Parent of `v`, or `None` for the root.
+    pub fn parent(&self, v: &Node) -> Option<Node> {
+        if self.is_root(v) {
+            return None;
+        }
+        let (start, end) = (v.range.start, v.range.end);
+        let k = if boundary(self.lcp, start) >= boundary(self.lcp, end) { start } else { end };
+        Some(self.enclosing(k))
+    }
+
+    /// Children of `v` in lexicographic order, empty for leaves.
+    pub fn children(&self, v: &Node) -> Vec<Node> {
+        if v.range.is_empty() || self.is_leaf(v) {
+            return Vec::new();
+        }
+        let node = self.node_of(v.range.clone());
+        if node.depth > v.depth {
+            // unary root, all suffixes sharing a non-empty prefix
+            return vec![node];
+        }
+        let mut children = Vec::new();
+        let mut start = v.range.start;
+        while start + 1 < v.range.end {
+            let k = self.rmq.argmin(start + 1..v.range.end);
+            if self.lcp[k].as_usize() != v.depth {
+                break;
+            }
+            children.push(self.node_of(start..k));
+            start = k;
+        }
+        children.push(self.node_of(start..v.range.end));
+        children
+    }
+
+    /// Child of `v` whose edge label starts with `c`, or `None` if there is none.
+    pub fn child(&self, v: &Node, c: u8) -> Option<Node> {
+        self.children(v).into_iter().find(|u| {
+            let p = self.csa.lookup(u.range.start) + v.depth;
+            p < self.len() && self.csa.symbol(p) == c
+        })
+    }
+
+    /// Lowest common ancestor of nodes `u` and `v`.
+    pub fn lca(&self, u: &Node, v: &Node) -> Node {
+        let contains = |x: &Node, y: &Node| x.range.start <= y.range.start && y.range.end <= x.range.end && x.depth <= y.depth;
+        if contains(u, v) {
+            return u.clone();
+        }
+        if contains(v, u) {
+            return v.clone();
+        }
+        self.lca_of_ranks(u.range.start, v.range.start)
+    }
+
+    /// Lowest common ancestor of leaves at distinct ranks `x` and `y`.
+    fn lca_of_ranks(&self, x: usize, y: usize) -> Node {
+        let k = self.rmq.argmin(Ord::min(x, y) + 1..Ord::max(x, y) + 1);
+        self.enclosing(k)
+    }
+
+    /// Suffix link of `v`, i.e. the node whose label is the label of `v` without its first symbol,
+    /// or `None` for the root.
+    pub fn suffix_link(&self, v: &Node) -> Option<Node> {
+        if self.is_root(v) {
+            return None;
+        }
+        if v.depth <= 1 {
+            return Some(self.root());
+        }
+        // every suffix below `v` is at least two symbols long, so that Ψ is defined on both ends
+        let first = self.csa.psi(v.range.start)?;
+        if self.is_leaf(v) {
+            return Some(Node {
+                depth: v.depth - 1,
+                range: first..first + 1,
+            });
+        }
+        Some(self.lca_of_ranks(first, self.csa.psi(v.range.end - 1)?))
+    }
+}
+
+/// Lcp value at rank `k`, shifted by one, with `0` beyond both ends.
+#[inline]
+fn boundary<I: SaIndex>(lcp: &[I], k: usize) -> usize {
+    if k == 0 || k == lcp.len() {
+        0
+    } else {
+        lcp[k].as_usize() + 1
+    }
+}
@@ -0,0 +1,131 @@
+//! Extended bwt of string collections, where every string has its own terminator.
+//!
+//! Terminators are ordered by index of their strings and smaller than all symbols, which is the convention of
+//! BCR and ropebwt. The collection bwt is derived from the generalized suffix array of all terminated strings,
+//! constructed by [`sais_int`].
+
+use crate::common::max_size;
+use crate::errors::Error;
+use crate::sais32::{sais_int, Result, MAX_LENGTH};
+
+/// Bwt of a string collection.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollectionBwt {
+    /// Bwt of all terminated strings, of length `sum(len + 1)`, where positions of terminators hold `0`.
+    pub bwt: Vec<u8>,
+    /// `terminators[i]` is the position in `bwt` of the terminator of `i`-th string.
+    pub terminators: Vec<i32>,
+}
+
+/// Computes bwt of a string collection, each string terminated by its own terminator.
+///
+/// Returns `Err(Error::IllegalArguments)` if the total length with terminators is larger than `MAX_LENGTH`.
+///
+/// # Examples
+///
+/// ```
+/// use sais::ebwt::{ebwt, unebwt};
+///
+/// let strings = [&b"ACGT"[..], b"", b"GATTACA"];
+/// let collection = ebwt(&strings).unwrap();
+/// assert_eq!(collection.bwt.len(), 4 + 1 + 0 + 1 + 7 + 1);
+/// assert_eq!(unebwt(&collection.bwt, &collection.terminators).unwrap(), strings);
+/// ```
+pub fn ebwt<S: AsRef<[u8]>>(strings: &[S]) -> Result<CollectionBwt> {
+    let m = strings.len();
+    let total = strings.iter().map(|s| s.as_ref().len() + 1).sum();
+    let n = max_size(total, MAX_LENGTH)?;
+    let k = max_size(m + 256, MAX_LENGTH)?;
+
+    // terminator of string i is i, symbol c is m + c
+    let mut t = Vec::with_capacity(n);
+    let mut string_starts = vec![false; n];
+    for (i, s) in strings.iter().enumerate() {
+        string_starts[t.len()] = true;
+        t.extend(s.as_ref().iter().map(|&c| (m + c as usize) as i32));
+        t.push(i as i32);
+    }
+    let mut sa = vec![0i32; n];
+    sais_int(t.clone().as_mut_slice(), sa.as_mut_slice(), k as i32)?;
+
+    let mut bwt = vec![0u8; n];
+    let mut terminators = vec![0i32; m];
+    for (row, &p) in sa.iter().enumerate() {
+        let p = p as usize;
+        if string_starts[p] {
+            // the string starting at p is terminated by the next terminator in the text
+            let end = (p..n).find(|&q| (t[q] as usize) < m).unwrap_or(p);
+            terminators[t[end] as usize] = row as i32;
+        } else {
+            bwt[row] = match t[p - 1] as usize {
+                c if c >= m => (c - m) as u8,
+                _ => 0,
+            };
+        }
+    }
+    Ok(CollectionBwt { bwt, terminators })
+}
+
+/// Inverts bwt of a string collection, returns strings in input order.
+///
+/// Returns `Err(Error::IllegalArguments)` if `terminators` are not distinct positions in `bwt`,
+/// or they are inconsistent with `bwt`.
+pub fn unebwt(bwt: &[u8], terminators: &[i32]) -> Result<Vec<Vec<u8>>> {
+    let (n, m) = (max_size(bwt.len(), MAX_LENGTH)?, terminators.len());
+    if m > n {
+        Err(Error::IllegalArguments)?
+    }
+
+    // terminator_of[row] is the string index of the terminator at row
+    let mut terminator_of = vec![usize::MAX; n];
+    for (i, &row) in terminators.iter().enumerate() {
+        if row < 0 || row as usize >= n || terminator_of[row as usize] != usize::MAX {
+            Err(Error::IllegalArguments)?
+        }
+        terminator_of[row as usize] = i;
+    }
+
+    // lf mapping, rows 0..m are terminator suffixes in string order
+    let mut buckets = [0usize; 257];
+    for (row, &c) in bwt.iter().enumerate() {
+        if terminator_of[row] == usize::MAX {
+            buckets[c as usize + 1] += 1;
+        }
+    }
+    buckets[0] = m;
+    for c in 0..256 {
+        buckets[c + 1] += buckets[c];
+    }
+    let mut lf = vec![0usize; n];
+    for (row, &c) in bwt.iter().enumerate() {
+        lf[row] = match terminator_of[row] {
+            usize::MAX => {
+                buckets[c as usize] += 1;
+                buckets[c as usize] - 1
+            }
+            i => i,
+        };
+    }
+
+    let mut strings = Vec::with_capacity(m);
+    let mut steps = 0;
+    for i in 0..m {
+        let mut s = Vec::new();
+        let mut row = i;
+        while terminator_of[row] == usize::MAX {
+            steps += 1;
+            if steps > n {
+                Err(Error::IllegalArguments)?
+            }
+            s.push(bwt[row]);
+            row = lf[row];
+        }
+        if terminator_of[row] != i {
+            Err(Error::IllegalArguments)?
+        }
+        s.reverse();
+        strings.push(s);
+    }
+    Ok(strings)
+}
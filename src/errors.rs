@@ -13,6 +13,7 @@ impl ReturnCode for i64 {}
 
 /// Interpreted error code from libsais.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Error<I: ReturnCode> {
     IllegalArguments,
     InternalError,
@@ -0,0 +1,222 @@
+//! Enhanced suffix array, i.e. bottom-up traversal of lcp-interval tree over suffix array and lcp array.
+//!
+//! An lcp-interval `[i, j)` with lcp value `l` is a maximal range of suffix array ranks whose suffixes share
+//! a common prefix of length `l`. Lcp-intervals form a tree equivalent to internal nodes of suffix tree,
+//! with suffixes (singleton ranges) being the leaves.
+
+use std::ops::Range;
+
+use crate::common::same_size;
+use crate::errors::Error;
+use crate::index::SaIndex;
+
+/// Suffix array together with its lcp array.
+#[derive(Debug, Copy, Clone)]
+pub struct EnhancedSuffixArray<'a, I: SaIndex> {
+    sa: &'a [I],
+    lcp: &'a [I],
+}
+
+impl<'a, I: SaIndex> EnhancedSuffixArray<'a, I> {
+    /// Pairs up suffix array and lcp array of the same text.
+    ///
+    /// Returns `Err(Error::IllegalArguments)` if both arrays differ in length, or `lcp` contains negative values.
+    ///
+    /// # Arguments
+    ///
+    /// * `sa` - Suffix array of the text, exactly `text.len()` elements without free space.
+    /// * `lcp` - Lcp array of the text, exactly `text.len()` elements.
+    pub fn new(sa: &'a [I], lcp: &'a [I]) -> Result<Self, Error<I>> {
+        same_size(sa.len(), lcp.len())?;
+        if lcp.iter().any(|&l| l < I::default()) {
+            Err(Error::IllegalArguments)?
+        }
+        Ok(EnhancedSuffixArray { sa, lcp })
+    }
+
+    /// Length of the text.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sa.len()
+    }
+
+    /// Whether the text is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sa.is_empty()
+    }
+
+    /// Suffix array.
+    #[inline]
+    pub fn suffix_array(&self) -> &'a [I] {
+        self.sa
+    }
+
+    /// Lcp array.
+    #[inline]
+    pub fn lcp_array(&self) -> &'a [I] {
+        self.lcp
+    }
+
+    /// Text positions of suffixes within a range of suffix array ranks.
+    #[inline]
+    pub fn suffixes(&self, interval: &LcpInterval) -> &'a [I] {
+        &self.sa[interval.range.clone()]
+    }
+
+    /// Iterates over lcp-intervals bottom-up, i.e. every interval is yielded after all of its child intervals.
+    ///
+    /// The root interval `[0, n)` is always yielded last for non-empty texts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sais::esa::EnhancedSuffixArray;
+    /// use sais::sais32::{lcp, plcp, sais};
+    ///
+    /// let t = b"mississippi";
+    /// let mut sa = vec![0; t.len()];
+    /// let mut plcp_array = vec![0; t.len()];
+    /// let mut lcp_array = vec![0; t.len()];
+    /// sais(t, &mut sa, None).unwrap();
+    /// plcp(t, &sa, &mut plcp_array).unwrap();
+    /// lcp(&plcp_array, &sa, &mut lcp_array).unwrap();
+    ///
+    /// let esa = EnhancedSuffixArray::new(&sa, &lcp_array).unwrap();
+    /// for interval in esa.intervals() {
+    ///     let pos = esa.suffixes(&interval)[0] as usize;
+    ///     println!("{:?} occurs {} times", &t[pos..pos + interval.lcp], interval.range.len());
+    /// }
+    /// ```
+    pub fn intervals(&self) -> LcpIntervals<'a, I> {
+        LcpIntervals::new(self.lcp)
+    }
+}
+
+/// Lcp-interval, i.e. internal node of the simulated suffix tree.
+///
+/// Deserialization checks that the range is non-empty and child boundaries increase strictly within it.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "LcpIntervalParts"))]
+pub struct LcpInterval {
+    /// Length of the longest common prefix of all suffixes in the interval.
+    pub lcp: usize,
+    /// Range of suffix array ranks.
+    pub range: Range<usize>,
+    /// Ranks in the interval at which a child interval starts, except the first child.
+    boundaries: Vec<usize>,
+}
+
+/// Fields of a deserialized lcp-interval, before validation.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct LcpIntervalParts {
+    lcp: usize,
+    range: Range<usize>,
+    boundaries: Vec<usize>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<LcpIntervalParts> for LcpInterval {
+    type Error = &'static str;
+
+    fn try_from(parts: LcpIntervalParts) -> Result<Self, Self::Error> {
+        let LcpIntervalParts { lcp, range, boundaries } = parts;
+        let mut previous = range.start;
+        for &k in boundaries.iter().chain([&range.end]) {
+            if k <= previous {
+                return Err("child boundaries out of order or range of lcp-interval");
+            }
+            previous = k;
+        }
+        Ok(LcpInterval { lcp, range, boundaries })
+    }
+}
+
+impl LcpInterval {
+    /// Ranges of child intervals in order, where singleton ranges are leaves.
+    pub fn children(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        let starts = std::iter::once(self.range.start).chain(self.boundaries.iter().copied());
+        let ends = self
+            .boundaries
+            .iter()
+            .copied()
+            .chain(std::iter::once(self.range.end));
+        starts.zip(ends).map(|(start, end)| start..end)
+    }
+
+    /// Number of child intervals, including leaves.
+    #[inline]
+    pub fn child_count(&self) -> usize {
+        self.boundaries.len() + 1
+    }
+
+    /// Whether the interval covers the whole suffix array.
+    #[inline]
+    pub fn is_root(&self, text_size: usize) -> bool {
+        self.range.start == 0 && self.range.end == text_size
+    }
+}
+
+/// Bottom-up iterator over lcp-intervals, see [`EnhancedSuffixArray::intervals`].
+pub struct LcpIntervals<'a, I: SaIndex> {
+    lcp: &'a [I],
+    stack: Vec<LcpInterval>,
+    rank: usize,
+    left_bound: usize,
+}
+
+impl<'a, I: SaIndex> LcpIntervals<'a, I> {
+    /// Iterates over lcp-intervals of an lcp array bottom-up.
+    pub fn new(lcp: &'a [I]) -> Self {
+        let mut stack = Vec::new();
+        if !lcp.is_empty() {
+            let root_lcp = lcp[1..].iter().copied().min().map(I::as_usize).unwrap_or(0);
+            stack.push(LcpInterval {
+                lcp: root_lcp,
+                range: 0..0,
+                boundaries: Vec::new(),
+            });
+        }
+        LcpIntervals {
+            lcp,
+            stack,
+            rank: 1,
+            left_bound: 0,
+        }
+    }
+}
+
+impl<'a, I: SaIndex> Iterator for LcpIntervals<'a, I> {
+    type Item = LcpInterval;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.rank < self.lcp.len() {
+            let l = self.lcp[self.rank].as_usize();
+            let top = self.stack.last_mut()?;
+            if l < top.lcp {
+                let mut interval = self.stack.pop()?;
+                interval.range.end = self.rank;
+                self.left_bound = interval.range.start;
+                return Some(interval);
+            }
+            if l > top.lcp {
+                let start = self.left_bound;
+                self.stack.push(LcpInterval {
+                    lcp: l,
+                    range: start..start,
+                    boundaries: vec![self.rank],
+                });
+            } else {
+                top.boundaries.push(self.rank);
+            }
+            self.rank += 1;
+            self.left_bound = self.rank - 1;
+        }
+
+        let mut interval = self.stack.pop()?;
+        interval.range.end = self.lcp.len();
+        Some(interval)
+    }
+}
@@ -0,0 +1,279 @@
+//! Random access substring extraction from a bwt with auxiliary indices, as produced by `bwt_aux`.
+//!
+//! Auxiliary index `aux[k]` is the row of suffix `k * rate` in the conceptual bwt matrix with a sentinel,
+//! where row `0` is the sentinel suffix. Text range `[start, end)` is extracted by LF-walking backwards
+//! from the nearest sampled suffix at or after `end`, which takes at most `rate + end - start` steps.
+//!
+//! Segments between samples are independent of each other, so that the whole text is also decoded in parallel
+//! by [`unbwt_aux_threads`] on rust threads, without the openmp based `parallel` feature.
+
+use std::ops::Range;
+
+use crate::aux_index::{aux_length_exact, aux_rate_exact};
+use crate::common::{lf_mapping, max_size, same_size};
+use crate::errors::Error;
+use crate::index::SaIndex;
+
+/// Owned output of `bwt_aux`, bundling the bwt with its primary index, auxiliary indices and symbol frequencies.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bwt<T, I> {
+    /// Bwt of the text, `n` elements.
+    pub bwt: Vec<T>,
+    /// Primary index of the bwt, equal to `aux[0]`.
+    pub primary: I,
+    /// Auxiliary indices, whose length determines the sampling rate.
+    pub aux: Vec<I>,
+    /// Symbol frequency table of the text, `FREQ_TABLE_SIZE` elements of the backend.
+    pub freq: Vec<I>,
+}
+
+impl<T: Copy + Default + Into<usize>, I: SaIndex> Bwt<T, I> {
+    /// Computes bwt of `t` with auxiliary indices by `bwt_aux` of a backend.
+    ///
+    /// Returns `Err(Error::IllegalArguments)` if `rate` is not a valid auxiliary sampling rate,
+    /// or errors returned by `bwt_aux`.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - Input text.
+    /// * `rate` - Auxiliary indices sampling rate, must be power of two and no less than `AUX_RATE_MIN`.
+    /// * `bwt_aux` - `bwt_aux` function of the backend, e.g. `sais32::bwt_aux`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sais::extract::Bwt;
+    /// use sais::sais32::{bwt_aux, unbwt_aux};
+    ///
+    /// let t = b"she sells seashells by the seashore";
+    /// let bwt = Bwt::new(t, 4, bwt_aux).unwrap();
+    /// assert_eq!(bwt.lf_index().unwrap().extract(10..19), b"seashells");
+    ///
+    /// let mut s = vec![0; t.len()];
+    /// let mut a = vec![0; t.len() + 1];
+    /// unbwt_aux(&bwt.bwt, &mut s, &mut a, Some(&bwt.freq), &bwt.aux).unwrap();
+    /// assert_eq!(s, t);
+    /// ```
+    pub fn new<F>(t: &[T], rate: usize, bwt_aux: F) -> Result<Self, Error<I>>
+    where
+        F: FnOnce(&[T], &mut [T], &mut [I], Option<&mut [I]>, &mut [I]) -> Result<(), Error<I>>,
+    {
+        let aux_length = aux_length_exact(t.len(), rate).ok_or(Error::IllegalArguments)?;
+        let mut bwt = vec![T::default(); t.len()];
+        let mut a = vec![I::default(); t.len()];
+        // one entry per value of the symbol type, i.e. FREQ_TABLE_SIZE of the backend
+        let mut freq = vec![I::default(); 1 << (8 * std::mem::size_of::<T>())];
+        let mut aux = vec![I::default(); aux_length];
+        bwt_aux(t, &mut bwt, &mut a, Some(&mut freq), &mut aux)?;
+        Ok(Bwt {
+            bwt,
+            primary: aux[0],
+            aux,
+            freq,
+        })
+    }
+
+    /// LF mapping of the bwt for random access extraction.
+    ///
+    /// Returns `Err(Error::IllegalArguments)` if the arrays are inconsistent, see [`LfIndex::new`].
+    pub fn lf_index(&self) -> Result<LfIndex<'_, T, I>, Error<I>> {
+        if self.aux.first() != Some(&self.primary) {
+            Err(Error::IllegalArguments)?
+        }
+        LfIndex::new(&self.bwt, &self.freq, &self.aux)
+    }
+}
+
+/// Precomputed LF mapping of a bwt with auxiliary indices, for repeated extraction.
+#[derive(Debug, Clone)]
+pub struct LfIndex<'a, T, I> {
+    bwt: &'a [T],
+    aux: &'a [I],
+    rate: usize,
+    primary: usize,
+    lf: Vec<I>,
+}
+
+impl<'a, T: Copy + Into<usize>, I: SaIndex> LfIndex<'a, T, I> {
+    /// Builds LF mapping of `bwt` with `n + 1` rows.
+    ///
+    /// Returns `Err(Error::IllegalArguments)` if `freq` does not match symbol frequencies of `bwt`,
+    /// or `aux` is not a valid auxiliary indices array of `bwt`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bwt` - Output of `bwt_aux`.
+    /// * `freq` - Symbol frequency table of the text, e.g. `FREQ_TABLE_SIZE` elements output by `bwt_aux`.
+    /// * `aux` - Auxiliary indices output by `bwt_aux`, whose length determines the sampling rate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sais::aux_index::aux_length_exact;
+    /// use sais::extract::LfIndex;
+    /// use sais::sais32::{bwt_aux, FREQ_TABLE_SIZE};
+    ///
+    /// let t = b"she sells seashells by the seashore";
+    /// let mut u = vec![0; t.len()];
+    /// let mut a = vec![0; t.len()];
+    /// let mut freq = vec![0; FREQ_TABLE_SIZE];
+    /// let mut aux = vec![0; aux_length_exact(t.len(), 4).unwrap()];
+    /// bwt_aux(t, &mut u, &mut a, Some(&mut freq), &mut aux).unwrap();
+    ///
+    /// let index = LfIndex::new(&u, &freq, &aux).unwrap();
+    /// assert_eq!(index.extract(10..19), b"seashells");
+    /// ```
+    pub fn new(bwt: &'a [T], freq: &[I], aux: &'a [I]) -> Result<Self, Error<I>> {
+        let n = max_size(bwt.len(), I::MAX_LENGTH - 1)?;
+        let rate = aux_rate_exact(n, aux.len()).ok_or(Error::IllegalArguments)?;
+        let primary = aux[0].as_usize();
+        if n > 0
+            && aux
+                .iter()
+                .any(|&row| row < I::from_usize(1) || row.as_usize() > n)
+        {
+            Err(Error::IllegalArguments)?
+        }
+
+        let lf = lf_mapping(bwt, primary, freq)?;
+        Ok(LfIndex { bwt, aux, rate, primary, lf })
+    }
+
+    #[inline]
+    fn symbol_at(bwt: &[T], primary: usize, row: usize) -> T {
+        if row < primary {
+            bwt[row]
+        } else {
+            bwt[row - 1]
+        }
+    }
+
+    /// Length of the text.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bwt.len()
+    }
+
+    /// Whether the text is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bwt.is_empty()
+    }
+
+    /// Sampling rate of auxiliary indices.
+    #[inline]
+    pub fn rate(&self) -> usize {
+        self.rate
+    }
+
+    /// Extracts text range `[start, end)` into `out`, which must have exactly `end - start` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of text bounds, or `out` differs in length from `range`.
+    pub fn extract_into(&self, range: Range<usize>, out: &mut [T]) {
+        let n = self.len();
+        assert!(range.start <= range.end && range.end <= n, "range {:?} out of text bounds {}", range, n);
+        assert_eq!(out.len(), range.len(), "output length differs from range length");
+        if range.is_empty() {
+            return;
+        }
+
+        let k = range.end.div_ceil(self.rate);
+        let (mut p, mut row) = match k * self.rate {
+            p if p < n => (p, self.aux[k].as_usize()),
+            _ => (n, 0),
+        };
+        while p > range.start {
+            p -= 1;
+            if p < range.end {
+                out[p - range.start] = Self::symbol_at(self.bwt, self.primary, row);
+            }
+            row = self.lf[row].as_usize();
+        }
+    }
+
+    /// Extracts text range `[start, end)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of text bounds.
+    pub fn extract(&self, range: Range<usize>) -> Vec<T> {
+        let mut out = Vec::with_capacity(range.len());
+        if let Some(&c) = self.bwt.first() {
+            out.resize(range.len(), c);
+        }
+        self.extract_into(range, &mut out);
+        out
+    }
+}
+
+/// Inverts bwt with auxiliary indices on `threads` rust threads, each decoding a range of sampled segments.
+///
+/// Returns `Err(Error::IllegalArguments)` if `t` and `u` differ in length, `freq` does not match symbol
+/// frequencies of `t`, `aux` is not a valid auxiliary indices array of `t`, or `threads` is negative.
+///
+/// # Arguments
+///
+/// * `t` - Output of `bwt_aux`.
+/// * `u` - Output text, exactly `t.len()` elements.
+/// * `freq` - Optional symbol frequency table, computed from `t` if `None`.
+/// * `aux` - Auxiliary indices output by `bwt_aux`.
+/// * `threads` - Number of threads, `0` for available parallelism, of the same type as the backend's `parallel` thread count.
+///
+/// # Examples
+///
+/// ```
+/// use sais::aux_index::aux_length_exact;
+/// use sais::extract::unbwt_aux_threads;
+/// use sais::sais32::bwt_aux;
+///
+/// let t = b"how much wood would a woodchuck chuck if a woodchuck could chuck wood";
+/// let mut u = vec![0; t.len()];
+/// let mut a = vec![0; t.len()];
+/// let mut aux = vec![0; aux_length_exact(t.len(), 8).unwrap()];
+/// bwt_aux(t, &mut u, &mut a, None, &mut aux).unwrap();
+///
+/// let mut s = vec![0; t.len()];
+/// unbwt_aux_threads(&u, &mut s, None, &aux, 4).unwrap();
+/// assert_eq!(s, t);
+/// ```
+pub fn unbwt_aux_threads<T, I>(t: &[T], u: &mut [T], freq: Option<&[I]>, aux: &[I], threads: I) -> Result<(), Error<I>>
+where
+    T: Copy + Into<usize> + Send + Sync,
+    I: SaIndex,
+{
+    let n = same_size(t.len(), u.len())?;
+    if threads < I::default() {
+        Err(Error::IllegalArguments)?
+    }
+    let counted;
+    let freq = match freq {
+        Some(freq) => freq,
+        None => {
+            let sigma = t.iter().map(|&c| c.into() + 1).max().unwrap_or(0);
+            let mut count = vec![0usize; sigma];
+            for &c in t {
+                count[c.into()] += 1;
+            }
+            counted = count.into_iter().map(I::from_usize).collect::<Vec<_>>();
+            &counted[..]
+        }
+    };
+    let index = LfIndex::new(t, freq, aux)?;
+
+    let threads = match threads.as_usize() {
+        0 => std::thread::available_parallelism().map_or(1, |p| p.get()),
+        threads => threads,
+    };
+    // segments start at sampled suffixes, so that no thread walks beyond its own segment
+    let segment = n.div_ceil(threads).div_ceil(index.rate()).max(1) * index.rate();
+    std::thread::scope(|scope| {
+        for (k, chunk) in u.chunks_mut(segment).enumerate() {
+            let index = &index;
+            scope.spawn(move || index.extract_into(k * segment..k * segment + chunk.len(), chunk));
+        }
+    });
+    Ok(())
+}
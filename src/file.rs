@@ -0,0 +1,483 @@
+//! Versioned binary index file format, loaded by memory mapping into borrowed slices without copying.
+//!
+//! An index file holds any of the text, suffix array, lcp array, bwt, auxiliary indices and frequency table
+//! built by one backend. All integers are little endian, and every section starts at a multiple of 8 bytes,
+//! so that a mapped file is reinterpreted in place on little endian hosts:
+//!
+//! ```text
+//! header   := magic "SAISIDX\0", version: u32, backend: u8 (16, 32 or 64), symbol_width: u8,
+//!             index_width: u8, 0: u8, text_length: u64, primary: u64 (u64::MAX if absent),
+//!             section_count: u32, checksum: u32, 0: [u8; 24]
+//! section  := kind: u32 (1 text, 2 sa, 3 lcp, 4 bwt, 5 aux, 6 freq), element_width: u32,
+//!             offset: u64, length: u64 (in elements)
+//! file     := header, section * section_count, payloads padded with zeros to 8 bytes
+//! ```
+//!
+//! The checksum is crc-32 of the whole file with the checksum field zeroed.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::aux_index::aux_rate_exact;
+use crate::common::{crc32_update, mapped_slice};
+
+/// Magic bytes of the file header.
+pub const MAGIC: &[u8; 8] = b"SAISIDX\0";
+
+/// Version of the file format written, and the latest version loaded.
+pub const VERSION: u32 = 1;
+
+const HEADER_SIZE: usize = 64;
+const SECTION_SIZE: usize = 24;
+const ALIGNMENT: usize = 8;
+const CHECKSUM_OFFSET: usize = 36;
+const NO_PRIMARY: u64 = u64::MAX;
+
+/// Backend which built the index, determining symbol and index widths.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Backend {
+    /// `u16` symbols and `i32` indices.
+    Sais16,
+    /// `u8` symbols and `i32` indices.
+    Sais32,
+    /// `u8` symbols and `i64` indices.
+    Sais64,
+}
+
+impl Backend {
+    fn from_widths(symbol_width: usize, index_width: usize) -> Option<Self> {
+        match (symbol_width, index_width) {
+            (2, 4) => Some(Backend::Sais16),
+            (1, 4) => Some(Backend::Sais32),
+            (1, 8) => Some(Backend::Sais64),
+            _ => None,
+        }
+    }
+
+    fn code(self) -> u8 {
+        match self {
+            Backend::Sais16 => 16,
+            Backend::Sais32 => 32,
+            Backend::Sais64 => 64,
+        }
+    }
+
+    fn from_code(code: u8) -> Option<Self> {
+        match code {
+            16 => Some(Backend::Sais16),
+            32 => Some(Backend::Sais32),
+            64 => Some(Backend::Sais64),
+            _ => None,
+        }
+    }
+
+    /// Size of symbols and indices in bytes.
+    fn widths(self) -> (usize, usize) {
+        match self {
+            Backend::Sais16 => (2, 4),
+            Backend::Sais32 => (1, 4),
+            Backend::Sais64 => (1, 8),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Kind {
+    Text = 1,
+    Sa = 2,
+    Lcp = 3,
+    Bwt = 4,
+    Aux = 5,
+    Freq = 6,
+}
+
+impl Kind {
+    const ALL: [Kind; 6] = [
+        Kind::Text,
+        Kind::Sa,
+        Kind::Lcp,
+        Kind::Bwt,
+        Kind::Aux,
+        Kind::Freq,
+    ];
+
+    fn is_symbol(self) -> bool {
+        matches!(self, Kind::Text | Kind::Bwt)
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for i32 {}
+    impl Sealed for i64 {}
+}
+
+/// Plain integer types stored in index files, i.e. `u8`/`u16` symbols and `i32`/`i64` indices.
+pub trait Element: Copy + private::Sealed {
+    /// Size in bytes.
+    const WIDTH: usize = std::mem::size_of::<Self>();
+}
+
+impl Element for u8 {}
+impl Element for u16 {}
+impl Element for i32 {}
+impl Element for i64 {}
+
+fn as_bytes<E: Element>(slice: &[E]) -> &[u8] {
+    // SAFETY: elements are plain integers without padding
+    unsafe { std::slice::from_raw_parts(slice.as_ptr() as *const u8, std::mem::size_of_val(slice)) }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn check_little_endian() -> io::Result<()> {
+    if cfg!(target_endian = "big") {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "index files require a little endian host"))?
+    }
+    Ok(())
+}
+
+/// Arrays of an index over a text of `len` symbols, each of which is optional.
+///
+/// Only serialization is derived, as borrowed arrays of wider elements cannot be deserialized in place.
+/// Serialized arrays deserialize into owned vectors of the same fields, or are loaded by [`MappedIndex`].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Index<'a, T, I> {
+    /// Length of the text.
+    pub len: usize,
+    /// Primary index of the bwt.
+    pub primary: Option<usize>,
+    /// Text, `len` elements.
+    pub text: Option<&'a [T]>,
+    /// Suffix array, `len` elements.
+    pub sa: Option<&'a [I]>,
+    /// Lcp array, `len` elements.
+    pub lcp: Option<&'a [I]>,
+    /// Bwt, `len` elements.
+    pub bwt: Option<&'a [T]>,
+    /// Auxiliary indices of the bwt.
+    pub aux: Option<&'a [I]>,
+    /// Symbol frequency table, `FREQ_TABLE_SIZE` elements of the backend.
+    pub freq: Option<&'a [I]>,
+}
+
+impl<'a, T: Element, I: Element> Index<'a, T, I> {
+    /// Index of a text of `len` symbols without any arrays, to be filled by struct update syntax.
+    pub fn new(len: usize) -> Self {
+        Index {
+            len,
+            primary: None,
+            text: None,
+            sa: None,
+            lcp: None,
+            bwt: None,
+            aux: None,
+            freq: None,
+        }
+    }
+
+    /// Backend corresponding to symbol type `T` and index type `I`.
+    pub fn backend(&self) -> io::Result<Backend> {
+        Backend::from_widths(T::WIDTH, I::WIDTH).ok_or_else(|| invalid_input("no backend has these symbol and index types"))
+    }
+
+    fn sections(&self) -> Vec<(Kind, &'a [u8])> {
+        let symbols = [(Kind::Text, self.text), (Kind::Bwt, self.bwt)];
+        let indices = [
+            (Kind::Sa, self.sa),
+            (Kind::Lcp, self.lcp),
+            (Kind::Aux, self.aux),
+            (Kind::Freq, self.freq),
+        ];
+        let mut sections: Vec<_> = symbols
+            .into_iter()
+            .filter_map(|(kind, s)| s.map(|s| (kind, as_bytes(s))))
+            .chain(
+                indices
+                    .into_iter()
+                    .filter_map(|(kind, s)| s.map(|s| (kind, as_bytes(s)))),
+            )
+            .collect();
+        sections.sort_by_key(|&(kind, _)| kind as u32);
+        sections
+    }
+
+    /// Writes the index in the file format.
+    ///
+    /// Returns error if lengths of the arrays are inconsistent with `len`, or the primary index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sais::file::{Index, MappedIndex};
+    /// use sais::sais32::sais;
+    ///
+    /// let t = b"mississippi";
+    /// let mut sa = vec![0; t.len()];
+    /// sais(t, &mut sa, None).unwrap();
+    ///
+    /// let mut bytes = Vec::new();
+    /// Index { text: Some(t), sa: Some(&sa), ..Index::new(t.len()) }.write_to(&mut bytes).unwrap();
+    ///
+    /// let mut mmap = memmap2::MmapMut::map_anon(bytes.len()).unwrap();
+    /// mmap.copy_from_slice(&bytes);
+    /// let mapped = MappedIndex::from_mmap(mmap.make_read_only().unwrap(), true).unwrap();
+    /// let index = mapped.index::<u8, i32>().unwrap();
+    /// assert_eq!(index.sa, Some(&sa[..]));
+    /// ```
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        check_little_endian()?;
+        let backend = self.backend()?;
+        self.validate(backend)?;
+
+        let sections = self.sections();
+        let mut header = Vec::with_capacity(HEADER_SIZE + sections.len() * SECTION_SIZE);
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&VERSION.to_le_bytes());
+        header.extend_from_slice(&[backend.code(), T::WIDTH as u8, I::WIDTH as u8, 0]);
+        header.extend_from_slice(&(self.len as u64).to_le_bytes());
+        header.extend_from_slice(&self.primary.map_or(NO_PRIMARY, |i| i as u64).to_le_bytes());
+        header.extend_from_slice(&(sections.len() as u32).to_le_bytes());
+        header.extend_from_slice(&0u32.to_le_bytes());
+        header.resize(HEADER_SIZE, 0);
+
+        let mut offset = HEADER_SIZE + sections.len() * SECTION_SIZE;
+        let mut paddings = Vec::with_capacity(sections.len());
+        for &(kind, bytes) in sections.iter() {
+            let width = if kind.is_symbol() { T::WIDTH } else { I::WIDTH };
+            header.extend_from_slice(&(kind as u32).to_le_bytes());
+            header.extend_from_slice(&(width as u32).to_le_bytes());
+            header.extend_from_slice(&(offset as u64).to_le_bytes());
+            header.extend_from_slice(&((bytes.len() / width) as u64).to_le_bytes());
+            let padding = bytes.len().next_multiple_of(ALIGNMENT) - bytes.len();
+            paddings.push(padding);
+            offset += bytes.len() + padding;
+        }
+
+        let zeros = [0u8; ALIGNMENT];
+        let mut crc = crc32_update(0, &header);
+        for (&(_, bytes), &padding) in sections.iter().zip(paddings.iter()) {
+            crc = crc32_update(crc32_update(crc, bytes), &zeros[..padding]);
+        }
+        header[CHECKSUM_OFFSET..CHECKSUM_OFFSET + 4].copy_from_slice(&crc.to_le_bytes());
+
+        writer.write_all(&header)?;
+        for (&(_, bytes), &padding) in sections.iter().zip(paddings.iter()) {
+            writer.write_all(bytes)?;
+            writer.write_all(&zeros[..padding])?;
+        }
+        writer.flush()
+    }
+
+    /// Writes the index into a new file at `path`, see [`write_to`](Index::write_to).
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_to(BufWriter::new(File::create(path)?))
+    }
+
+    fn validate(&self, backend: Backend) -> io::Result<()> {
+        let n = self.len;
+        let lengths = [
+            self.text.map(<[T]>::len),
+            self.sa.map(<[I]>::len),
+            self.lcp.map(<[I]>::len),
+            self.bwt.map(<[T]>::len),
+        ];
+        if lengths.into_iter().flatten().any(|length| length != n) {
+            Err(invalid_input("array length differs from text length"))?
+        }
+        if self
+            .aux
+            .is_some_and(|aux| aux_rate_exact(n, aux.len()).is_none())
+        {
+            Err(invalid_input("invalid auxiliary indices length"))?
+        }
+        if self
+            .freq
+            .is_some_and(|freq| freq.len() != 1 << (8 * backend.widths().0))
+        {
+            Err(invalid_input("invalid frequency table length"))?
+        }
+        if self.primary.is_some_and(|i| i > n) {
+            Err(invalid_input("primary index out of bounds"))?
+        }
+        Ok(())
+    }
+}
+
+/// Index file mapped into memory, whose arrays are borrowed by [`index`](MappedIndex::index).
+#[derive(Debug)]
+pub struct MappedIndex {
+    mmap: Mmap,
+    backend: Backend,
+    len: usize,
+    primary: Option<usize>,
+    sections: Vec<(Kind, Range<usize>)>,
+}
+
+impl MappedIndex {
+    /// Maps an index file and validates its header, optionally verifying the checksum of the whole file.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while mapped, see [`Mmap::map`].
+    pub unsafe fn open<P: AsRef<Path>>(path: P, verify_checksum: bool) -> io::Result<Self> {
+        let mmap = Mmap::map(&File::open(path)?)?;
+        Self::from_mmap(mmap, verify_checksum)
+    }
+
+    /// Validates header of a mapped index file, optionally verifying the checksum of the whole file.
+    ///
+    /// Returns error with `InvalidData` kind if the file is malformed or corrupted,
+    /// and `Unsupported` kind if it has a newer version.
+    pub fn from_mmap(mmap: Mmap, verify_checksum: bool) -> io::Result<Self> {
+        check_little_endian()?;
+        let bytes = &mmap[..];
+        if bytes.len() < HEADER_SIZE || &bytes[..MAGIC.len()] != MAGIC {
+            Err(invalid_data("not an index file"))?
+        }
+        let u32_at = |p: usize| u32::from_le_bytes(bytes[p..p + 4].try_into().unwrap());
+        let u64_at = |p: usize| u64::from_le_bytes(bytes[p..p + 8].try_into().unwrap());
+        let usize_at = |p: usize| usize::try_from(u64_at(p)).map_err(|_| invalid_data("size overflow"));
+
+        let version = u32_at(8);
+        if version == 0 || version > VERSION {
+            Err(io::Error::new(io::ErrorKind::Unsupported, format!("unsupported index file version {}", version)))?
+        }
+        let backend = Backend::from_code(bytes[12]).ok_or_else(|| invalid_data("unknown backend"))?;
+        let (symbol_width, index_width) = backend.widths();
+        if (bytes[13] as usize, bytes[14] as usize) != (symbol_width, index_width) {
+            Err(invalid_data("element widths differ from backend"))?
+        }
+        let len = usize_at(16)?;
+        let primary = match u64_at(24) {
+            NO_PRIMARY => None,
+            _ => Some(usize_at(24)?),
+        };
+        let count = u32_at(32) as usize;
+        let table_end = HEADER_SIZE + count * SECTION_SIZE;
+        if table_end > bytes.len() {
+            Err(invalid_data("truncated section table"))?
+        }
+
+        let mut sections: Vec<(Kind, Range<usize>)> = Vec::with_capacity(count);
+        for k in 0..count {
+            let entry = HEADER_SIZE + k * SECTION_SIZE;
+            let code = u32_at(entry);
+            let kind = Kind::ALL
+                .into_iter()
+                .find(|&kind| kind as u32 == code)
+                .ok_or_else(|| invalid_data("unknown section kind"))?;
+            if sections.iter().any(|&(other, _)| other == kind) {
+                Err(invalid_data("duplicate section"))?
+            }
+            let width = if kind.is_symbol() { symbol_width } else { index_width };
+            if u32_at(entry + 4) as usize != width {
+                Err(invalid_data("element width differs from backend"))?
+            }
+            let (offset, length) = (usize_at(entry + 8)?, usize_at(entry + 16)?);
+            let end = length
+                .checked_mul(width)
+                .and_then(|size| size.checked_add(offset))
+                .ok_or_else(|| invalid_data("size overflow"))?;
+            if !offset.is_multiple_of(ALIGNMENT) || offset < table_end || end > bytes.len() {
+                Err(invalid_data("section out of bounds"))?
+            }
+            let valid = match kind {
+                Kind::Aux => aux_rate_exact(len, length).is_some(),
+                Kind::Freq => length == 1 << (8 * symbol_width),
+                _ => length == len,
+            };
+            if !valid {
+                Err(invalid_data("invalid section length"))?
+            }
+            sections.push((kind, offset..end));
+        }
+        if primary.is_some_and(|i| i > len) {
+            Err(invalid_data("primary index out of bounds"))?
+        }
+
+        if verify_checksum {
+            let expected = u32_at(CHECKSUM_OFFSET);
+            let crc = crc32_update(0, &bytes[..CHECKSUM_OFFSET]);
+            let crc = crc32_update(crc, &[0; 4]);
+            if crc32_update(crc, &bytes[CHECKSUM_OFFSET + 4..]) != expected {
+                Err(invalid_data("checksum mismatch"))?
+            }
+        }
+
+        Ok(MappedIndex {
+            mmap,
+            backend,
+            len,
+            primary,
+            sections,
+        })
+    }
+
+    /// Backend which built the index.
+    #[inline]
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// Length of the text.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the text is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Primary index of the bwt, if stored.
+    #[inline]
+    pub fn primary(&self) -> Option<usize> {
+        self.primary
+    }
+
+    fn section<E: Element>(&self, kind: Kind) -> io::Result<Option<&[E]>> {
+        let Some((_, range)) = self.sections.iter().find(|&&(other, _)| other == kind) else {
+            return Ok(None);
+        };
+        mapped_slice(&self.mmap[range.clone()])
+            .map(Some)
+            .ok_or_else(|| invalid_data("misaligned section"))
+    }
+
+    /// Borrows arrays of the mapped file, which are usable by search APIs without copying.
+    ///
+    /// Returns error with `InvalidInput` kind if `T` and `I` do not match the backend of the file.
+    pub fn index<T: Element, I: Element>(&self) -> io::Result<Index<'_, T, I>> {
+        if Backend::from_widths(T::WIDTH, I::WIDTH) != Some(self.backend) {
+            Err(invalid_input("symbol and index types differ from backend"))?
+        }
+        Ok(Index {
+            len: self.len,
+            primary: self.primary,
+            text: self.section(Kind::Text)?,
+            sa: self.section(Kind::Sa)?,
+            lcp: self.section(Kind::Lcp)?,
+            bwt: self.section(Kind::Bwt)?,
+            aux: self.section(Kind::Aux)?,
+            freq: self.section(Kind::Freq)?,
+        })
+    }
+}
@@ -0,0 +1,53 @@
+//! Integer types of suffix array elements produced by sais algorithms.
+
+use crate::errors::ReturnCode;
+
+use std::hash::Hash;
+
+/// Suffix array element types, i.e. `i32` for 16/32-bit sais algorithms and `i64` for 64-bit sais algorithms.
+///
+/// Higher level algorithms built on top of suffix arrays, lcp arrays and bwt are generic over this trait.
+pub trait SaIndex: ReturnCode + Ord + Hash + Default + Send + Sync + 'static {
+    /// Maximum array length the index type is able to address.
+    const MAX_LENGTH: usize;
+
+    /// Converts a position into index type, the position must be no more than `MAX_LENGTH`.
+    fn from_usize(n: usize) -> Self;
+
+    /// Converts a non-negative index into position.
+    fn as_usize(self) -> usize;
+}
+
+#[cfg(any(feature = "sais16", feature = "sais32"))]
+impl SaIndex for i32 {
+    const MAX_LENGTH: usize = i32::MAX as usize;
+
+    #[inline]
+    fn from_usize(n: usize) -> Self {
+        debug_assert!(n <= Self::MAX_LENGTH);
+        n as i32
+    }
+
+    #[inline]
+    fn as_usize(self) -> usize {
+        debug_assert!(self >= 0);
+        self as usize
+    }
+}
+
+#[cfg(feature = "sais64")]
+impl SaIndex for i64 {
+    const MAX_LENGTH: usize = i64::MAX as usize;
+
+    #[inline]
+    fn from_usize(n: usize) -> Self {
+        debug_assert!(n <= Self::MAX_LENGTH);
+        n as i64
+    }
+
+    #[inline]
+    fn as_usize(self) -> usize {
+        debug_assert!(self >= 0);
+        self as usize
+    }
+}
@@ -0,0 +1,87 @@
+//! Longest common extension queries on top of suffix array and lcp array.
+
+use crate::common::{inverse_suffix_array, same_size};
+use crate::errors::Error;
+use crate::index::SaIndex;
+use crate::rmq::RangeMinimum;
+
+/// Index answering longest common extension queries in constant time.
+///
+/// `lce(i, j)` is the length of the longest common prefix of suffixes `t[i..]` and `t[j..]`,
+/// computed as a range minimum over the lcp array between the ranks of both suffixes.
+/// Preprocessing takes linear time and space, for the inverse suffix array and the range minimum structure
+/// over a copy of the lcp array.
+pub struct LceIndex<I: SaIndex> {
+    isa: Vec<I>,
+    rmq: RangeMinimum<I>,
+}
+
+impl<I: SaIndex> LceIndex<I> {
+    /// Builds longest common extension index from suffix array and lcp array.
+    ///
+    /// Returns `Err(Error::IllegalArguments)` if both arrays differ in length, or `sa` is not a permutation.
+    ///
+    /// # Arguments
+    ///
+    /// * `sa` - Suffix array of the text, exactly `text.len()` elements without free space.
+    /// * `lcp` - Lcp array of the text, exactly `text.len()` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sais::lce::LceIndex;
+    /// use sais::sais32::{lcp, plcp, sais};
+    ///
+    /// let t = b"mississippi";
+    /// let mut sa = vec![0; t.len()];
+    /// let mut plcp_array = vec![0; t.len()];
+    /// let mut lcp_array = vec![0; t.len()];
+    /// sais(t, &mut sa, None).unwrap();
+    /// plcp(t, &sa, &mut plcp_array).unwrap();
+    /// lcp(&plcp_array, &sa, &mut lcp_array).unwrap();
+    ///
+    /// let index = LceIndex::new(&sa, &lcp_array).unwrap();
+    /// assert_eq!(index.lce(1, 4), 4);
+    /// assert_eq!(index.lce(0, 1), 0);
+    /// ```
+    pub fn new(sa: &[I], lcp: &[I]) -> Result<Self, Error<I>> {
+        same_size(sa.len(), lcp.len())?;
+        let isa = inverse_suffix_array(sa)?;
+        let rmq = RangeMinimum::new(lcp.to_vec());
+        Ok(LceIndex { isa, rmq })
+    }
+
+    /// Length of the indexed text.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.isa.len()
+    }
+
+    /// Whether the indexed text is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.isa.is_empty()
+    }
+
+    /// Inverse suffix array, i.e. rank of each suffix.
+    #[inline]
+    pub fn inverse_suffix_array(&self) -> &[I] {
+        self.isa.as_slice()
+    }
+
+    /// Longest common extension of suffixes starting at text positions `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is out of bounds.
+    #[inline]
+    pub fn lce(&self, i: usize, j: usize) -> usize {
+        let (ri, rj) = (self.isa[i].as_usize(), self.isa[j].as_usize());
+        if ri == rj {
+            self.len() - i
+        } else {
+            let (lo, hi) = (Ord::min(ri, rj), Ord::max(ri, rj));
+            self.rmq.min(lo + 1..hi + 1).as_usize()
+        }
+    }
+}
@@ -1,9 +1,63 @@
 pub mod errors;
 
+pub mod index;
+
 mod common;
 
+mod rmq;
+
+mod rotation;
+
 pub mod aux_index;
 
+pub mod lce;
+
+pub mod esa;
+
+pub mod repeats;
+
+#[cfg(feature = "sais32")]
+pub mod matches;
+
+pub mod lz77;
+
+pub mod lyndon;
+
+pub mod extract;
+
+pub mod sentinel;
+
+pub mod csa;
+
+pub mod cst;
+
+#[cfg(feature = "mmap")]
+pub mod file;
+
+#[cfg(feature = "mmap")]
+pub mod mapped;
+
+#[cfg(feature = "sais32")]
+pub mod ebwt;
+
+#[cfg(feature = "sais32")]
+pub mod compress;
+
+#[cfg(feature = "sais32")]
+pub mod stream;
+
+#[cfg(feature = "sais32")]
+pub mod alphabet;
+
+#[cfg(feature = "sais32")]
+pub mod unicode;
+
+#[cfg(feature = "sais32")]
+pub mod sparse;
+
+#[cfg(feature = "sais32")]
+pub mod token;
+
 #[cfg(feature = "sais16")]
 pub mod sais16;
 
@@ -0,0 +1,93 @@
+//! Lyndon array and lyndon factorization, derived from the inverse suffix array.
+//!
+//! The longest lyndon word starting at position `i` ends right before the next suffix smaller than `t[i..]`,
+//! so the lyndon array is the next smaller value array of the inverse suffix array.
+
+use std::ops::Range;
+
+use crate::common::{max_size, same_size};
+use crate::errors::Error;
+use crate::index::SaIndex;
+
+/// Computes lyndon array from suffix array, `lyndon[i]` is the length of the longest lyndon word starting at `i`.
+///
+/// Returns `Err(Error::IllegalArguments)` if both arrays differ in length, or `sa` is not a permutation.
+///
+/// # Arguments
+///
+/// * `sa` - Suffix array of the text, exactly `text.len()` elements without free space.
+/// * `lyndon` - Output lyndon array, exactly `text.len()` elements.
+///
+/// # Examples
+///
+/// ```
+/// use sais::lyndon::lyndon_array;
+/// use sais::sais32::sais;
+///
+/// let t = b"banana";
+/// let mut sa = vec![0; t.len()];
+/// let mut lyndon = vec![0; t.len()];
+/// sais(t, &mut sa, None).unwrap();
+/// lyndon_array(&sa, &mut lyndon).unwrap();
+/// assert_eq!(lyndon, [1, 2, 1, 2, 1, 1]);
+/// ```
+pub fn lyndon_array<I: SaIndex>(sa: &[I], lyndon: &mut [I]) -> Result<(), Error<I>> {
+    let n = max_size(same_size(sa.len(), lyndon.len())?, I::MAX_LENGTH)?;
+    // fill inverse suffix array in place, where no rank equals n
+    lyndon.fill(I::from_usize(n));
+    for (rank, &p) in sa.iter().enumerate() {
+        if p < I::default() || p.as_usize() >= n || lyndon[p.as_usize()].as_usize() != n {
+            Err(Error::IllegalArguments)?
+        }
+        lyndon[p.as_usize()] = I::from_usize(rank);
+    }
+
+    // positions after i with increasing ranks from top to bottom, paired with their ranks
+    let mut stack: Vec<(usize, I)> = Vec::new();
+    for i in (0..n).rev() {
+        let rank = lyndon[i];
+        while stack.last().is_some_and(|&(_, r)| r > rank) {
+            stack.pop();
+        }
+        let next_smaller = stack.last().map_or(n, |&(j, _)| j);
+        lyndon[i] = I::from_usize(next_smaller - i);
+        stack.push((i, rank));
+    }
+    Ok(())
+}
+
+/// Lyndon factorization of the text from its lyndon array, i.e. ranges of lexicographically non-increasing lyndon words.
+///
+/// # Panics
+///
+/// Panics if `lyndon` is not a valid lyndon array.
+///
+/// # Examples
+///
+/// ```
+/// use sais::lyndon::{lyndon_array, lyndon_factorization};
+/// use sais::sais32::sais;
+///
+/// let t = b"banana";
+/// let mut sa = vec![0; t.len()];
+/// let mut lyndon = vec![0; t.len()];
+/// sais(t, &mut sa, None).unwrap();
+/// lyndon_array(&sa, &mut lyndon).unwrap();
+///
+/// let factors: Vec<&[u8]> = lyndon_factorization(&lyndon).map(|range| &t[range]).collect();
+/// assert_eq!(factors, [&b"b"[..], b"an", b"an", b"a"]);
+/// ```
+pub fn lyndon_factorization<I: SaIndex>(lyndon: &[I]) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut start = 0;
+    std::iter::from_fn(move || {
+        if start < lyndon.len() {
+            let length = lyndon[start].as_usize();
+            assert!(length > 0, "invalid lyndon array");
+            let factor = start..start + length;
+            start += length;
+            Some(factor)
+        } else {
+            None
+        }
+    })
+}
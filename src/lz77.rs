@@ -0,0 +1,159 @@
+//! Lempel-Ziv 77 factorization and longest previous factor array, computed from suffix array and lcp array.
+//!
+//! Both are computed in linear time, with previous and next smaller values of the suffix array. Algorithms are
+//! generic over suffix array element types, thus `i64` arrays from 64-bit sais algorithms work for large inputs.
+
+use crate::common::{max_size, same_size};
+use crate::errors::Error;
+use crate::index::SaIndex;
+
+/// Phrase of greedy lz77 factorization.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Phrase<T> {
+    /// Symbol not occurring before.
+    Literal(T),
+    /// Copy of `length` symbols starting at earlier position `source`, which may overlap with the phrase itself.
+    Copy { source: usize, length: usize },
+}
+
+impl<T> Phrase<T> {
+    /// Number of text symbols covered by the phrase.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Phrase::Literal(_) => 1,
+            Phrase::Copy { length, .. } => *length,
+        }
+    }
+
+    /// Whether the phrase covers no symbol, which never happens for phrases of a factorization.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Longest previous factor array with one previous occurrence of each factor.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LongestPreviousFactor<I: SaIndex> {
+    /// `lpf[i]` is the length of longest prefix of `t[i..]` occurring at some position before `i`.
+    pub lpf: Vec<I>,
+    /// `prev_occ[i]` is a position before `i` where the longest previous factor occurs, or `i` if `lpf[i] == 0`.
+    pub prev_occ: Vec<I>,
+}
+
+/// Computes longest previous factor array from suffix array and lcp array.
+///
+/// Returns `Err(Error::IllegalArguments)` if both arrays differ in length, or `sa` contains out of range values.
+///
+/// # Arguments
+///
+/// * `sa` - Suffix array of the text, exactly `text.len()` elements without free space.
+/// * `lcp` - Lcp array of the text, exactly `text.len()` elements.
+pub fn longest_previous_factor<I: SaIndex>(sa: &[I], lcp: &[I]) -> Result<LongestPreviousFactor<I>, Error<I>> {
+    let n = max_size(same_size(sa.len(), lcp.len())?, I::MAX_LENGTH)?;
+    if sa.iter().any(|&p| p < I::default() || p.as_usize() >= n) {
+        Err(Error::IllegalArguments)?
+    }
+
+    let mut lpf = vec![I::default(); n];
+    let mut prev_occ = vec![I::default(); n];
+    // ranks with increasing suffix positions, each paired with lcp of itself and the rank below
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+    for rank in 0..=n {
+        // lcp of the stack top and current rank
+        let mut common = if rank < n { lcp[rank].as_usize() } else { 0 };
+        while let Some(&(top, top_common)) = stack.last() {
+            if rank < n && sa[top] < sa[rank] {
+                break;
+            }
+            stack.pop();
+            let p = sa[top].as_usize();
+            let (length, source) = match stack.last() {
+                Some(&(below, _)) if top_common >= common && top_common > 0 => (top_common, sa[below].as_usize()),
+                _ if common > 0 => (common, sa[rank].as_usize()),
+                _ => (0, p),
+            };
+            lpf[p] = I::from_usize(length);
+            prev_occ[p] = I::from_usize(source);
+            common = Ord::min(common, top_common);
+        }
+        if rank < n {
+            stack.push((rank, if stack.is_empty() { 0 } else { common }));
+        }
+    }
+    Ok(LongestPreviousFactor { lpf, prev_occ })
+}
+
+/// Computes greedy lz77 factorization from text, suffix array and lcp array.
+///
+/// Returns `Err(Error::IllegalArguments)` if the arrays differ in length, or `sa` contains out of range values.
+///
+/// # Arguments
+///
+/// * `t` - Input text.
+/// * `sa` - Suffix array of the text, exactly `text.len()` elements without free space.
+/// * `lcp` - Lcp array of the text, exactly `text.len()` elements.
+///
+/// # Examples
+///
+/// ```
+/// use sais::lz77::{lz77, lz77_decode, Phrase};
+/// use sais::sais32::{lcp, plcp, sais};
+///
+/// let t = b"abababc";
+/// let mut sa = vec![0; t.len()];
+/// let mut plcp_array = vec![0; t.len()];
+/// let mut lcp_array = vec![0; t.len()];
+/// sais(t, &mut sa, None).unwrap();
+/// plcp(t, &sa, &mut plcp_array).unwrap();
+/// lcp(&plcp_array, &sa, &mut lcp_array).unwrap();
+///
+/// let phrases = lz77(t, &sa, &lcp_array).unwrap();
+/// assert_eq!(phrases, [Phrase::Literal(b'a'), Phrase::Literal(b'b'), Phrase::Copy { source: 0, length: 4 }, Phrase::Literal(b'c')]);
+/// assert_eq!(lz77_decode(&phrases), t);
+/// ```
+pub fn lz77<T: Copy, I: SaIndex>(t: &[T], sa: &[I], lcp: &[I]) -> Result<Vec<Phrase<T>>, Error<I>> {
+    same_size(t.len(), sa.len())?;
+    let LongestPreviousFactor { lpf, prev_occ } = longest_previous_factor(sa, lcp)?;
+
+    let mut phrases = Vec::new();
+    let mut i = 0;
+    while i < t.len() {
+        let length = lpf[i].as_usize();
+        if length == 0 {
+            phrases.push(Phrase::Literal(t[i]));
+            i += 1;
+        } else {
+            phrases.push(Phrase::Copy {
+                source: prev_occ[i].as_usize(),
+                length,
+            });
+            i += length;
+        }
+    }
+    Ok(phrases)
+}
+
+/// Reconstructs text from lz77 phrases.
+///
+/// # Panics
+///
+/// Panics if a copy phrase refers to a position not yet decoded.
+pub fn lz77_decode<T: Copy>(phrases: &[Phrase<T>]) -> Vec<T> {
+    let mut t = Vec::with_capacity(phrases.iter().map(Phrase::len).sum());
+    for phrase in phrases {
+        match *phrase {
+            Phrase::Literal(c) => t.push(c),
+            Phrase::Copy { source, length } => {
+                assert!(source < t.len(), "lz77 phrase refers to undecoded position");
+                for k in source..source + length {
+                    t.push(t[k]);
+                }
+            }
+        }
+    }
+    t
+}
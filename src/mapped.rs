@@ -0,0 +1,68 @@
+//! Memory-mapped texts and output arrays, for texts whose arrays do not fit in memory alongside the text.
+//!
+//! Backends provide `mapped` variants of suffix array and lcp construction, e.g.
+//! `sais64::mapped::sais`, over byte buffers such as [`Mmap`] and [`MmapMut`],
+//! which are reinterpreted as index arrays. A buffer whose length or alignment does not fit the index type
+//! is rejected with `Error::IllegalArguments`, and the reinterpreted arrays go through the same length checks
+//! as slices, so that an output buffer longer than the text leaves free space to the algorithm.
+
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+
+use memmap2::{Mmap, MmapMut};
+
+use crate::file::Element;
+
+/// Maps a text file for reading.
+///
+/// # Safety
+///
+/// The file must not be modified or truncated while mapped, see [`Mmap::map`].
+pub unsafe fn map_input<P: AsRef<Path>>(path: P) -> io::Result<Mmap> {
+    Mmap::map(&File::open(path)?)
+}
+
+/// Creates or truncates a file of `len` elements of index type `I`, and maps it for writing.
+///
+/// Contents are written back to the file when the map is flushed or dropped. The file holds the raw array
+/// in native byte order without any header, so unlike the [`crate::file`] format it carries no length, type
+/// or checksum, and is not portable across endianness.
+///
+/// # Examples
+///
+/// ```
+/// use sais::mapped::{create_output, map_input};
+/// use sais::sais32::mapped::sais;
+///
+/// let dir = std::env::temp_dir();
+/// let text_path = dir.join(format!("sais-doc-{}.txt", std::process::id()));
+/// let sa_path = dir.join(format!("sais-doc-{}.sa", std::process::id()));
+/// std::fs::write(&text_path, b"mississippi").unwrap();
+///
+/// let t = unsafe { map_input(&text_path) }.unwrap();
+/// let mut sa = create_output::<i32, _>(&sa_path, t.len()).unwrap();
+/// sais(&t, &mut sa, None).unwrap();
+/// sa.flush().unwrap();
+/// drop((t, sa));
+///
+/// let bytes = std::fs::read(&sa_path).unwrap();
+/// assert_eq!(bytes[..4], 10i32.to_ne_bytes());
+/// std::fs::remove_file(text_path).unwrap();
+/// std::fs::remove_file(sa_path).unwrap();
+/// ```
+pub fn create_output<I: Element, P: AsRef<Path>>(path: P, len: usize) -> io::Result<MmapMut> {
+    let size = len
+        .checked_mul(I::WIDTH)
+        .and_then(|size| u64::try_from(size).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "output size overflow"))?;
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    file.set_len(size)?;
+    // SAFETY: the file is created by us, modifying it elsewhere while mapped is up to the caller
+    unsafe { MmapMut::map_mut(&file) }
+}
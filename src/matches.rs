@@ -0,0 +1,239 @@
+//! Longest common substring, common substrings and maximal exact matches between two texts.
+//!
+//! Both texts are concatenated with a unique separator symbol into a generalized text, whose suffix array
+//! is constructed by [`sais_int`] and lcp array by linear time kasai algorithm.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::common::{lcp_array, max_size};
+use crate::esa::LcpIntervals;
+use crate::index::SaIndex;
+use crate::sais32::{sais_int, Result, MAX_LENGTH};
+
+/// Exact match between two texts.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Match {
+    /// Start position in the first text.
+    pub a: usize,
+    /// Start position in the second text.
+    pub b: usize,
+    /// Length of the match.
+    pub length: usize,
+}
+
+impl Match {
+    /// Matched range in the first text.
+    #[inline]
+    pub fn range_a(&self) -> Range<usize> {
+        self.a..self.a + self.length
+    }
+
+    /// Matched range in the second text.
+    #[inline]
+    pub fn range_b(&self) -> Range<usize> {
+        self.b..self.b + self.length
+    }
+}
+
+/// Right-maximal substring occurring in both texts, with all of its occurrences.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommonSubstring {
+    /// Length of the common substring.
+    pub length: usize,
+    /// Start positions in the first text, in suffix array order.
+    pub positions_a: Vec<usize>,
+    /// Start positions in the second text, in suffix array order.
+    pub positions_b: Vec<usize>,
+}
+
+/// Generalized suffix array and lcp array of two texts.
+pub struct GeneralizedSuffixArray<'a> {
+    a: &'a [u8],
+    b: &'a [u8],
+    sa: Vec<i32>,
+    lcp: Vec<i32>,
+}
+
+/// Origin of a generalized suffix.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+enum Origin {
+    A(usize),
+    B(usize),
+    Separator,
+}
+
+impl<'a> GeneralizedSuffixArray<'a> {
+    /// Builds generalized suffix array and lcp array of `a`, separator and `b`.
+    ///
+    /// Returns `Err(Error::IllegalArguments)` if the generalized text is longer than `MAX_LENGTH`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sais::matches::GeneralizedSuffixArray;
+    ///
+    /// let gsa = GeneralizedSuffixArray::new(b"xabcdy", b"zzabcdzz").unwrap();
+    /// let lcs = gsa.longest_common_substring().unwrap();
+    /// assert_eq!((lcs.a, lcs.b, lcs.length), (1, 2, 4));
+    /// ```
+    pub fn new(a: &'a [u8], b: &'a [u8]) -> Result<Self> {
+        let n = max_size(a.len().saturating_add(b.len()).saturating_add(1), MAX_LENGTH)?;
+        let mut t = Vec::with_capacity(n);
+        t.extend(a.iter().map(|&c| c as i32));
+        t.push(256);
+        t.extend(b.iter().map(|&c| c as i32));
+
+        let mut sa = vec![0i32; n];
+        sais_int(t.clone().as_mut_slice(), sa.as_mut_slice(), 257)?;
+        let lcp = lcp_array(t.as_slice(), sa.as_slice())?;
+        Ok(GeneralizedSuffixArray { a, b, sa, lcp })
+    }
+
+    /// Generalized suffix array, where `a.len()` is the separator and positions after it belong to `b`.
+    #[inline]
+    pub fn suffix_array(&self) -> &[i32] {
+        self.sa.as_slice()
+    }
+
+    /// Lcp array of the generalized suffix array.
+    #[inline]
+    pub fn lcp_array(&self) -> &[i32] {
+        self.lcp.as_slice()
+    }
+
+    /// Finds a longest substring occurring in both texts.
+    ///
+    /// Returns `None` if both texts share no symbol.
+    pub fn longest_common_substring(&self) -> Option<Match> {
+        let mut longest: Option<Match> = None;
+        for k in 1..self.sa.len() {
+            let length = self.lcp[k].as_usize();
+            if length == 0 || longest.is_some_and(|m| m.length >= length) {
+                continue;
+            }
+            if let Some((a, b)) = pair(self.origin(k - 1), self.origin(k)) {
+                longest = Some(Match { a, b, length });
+            }
+        }
+        longest
+    }
+
+    /// Enumerates right-maximal common substrings no shorter than `min_length`, with occurrences in both texts.
+    ///
+    /// Every reported substring occurs in both texts and cannot be extended to the right without losing
+    /// an occurrence, which correspond to internal nodes of the generalized suffix tree.
+    pub fn common_substrings(&self, min_length: usize) -> impl Iterator<Item = CommonSubstring> + '_ {
+        let min_length = Ord::max(min_length, 1);
+        LcpIntervals::new(self.lcp.as_slice()).filter_map(move |interval| {
+            if interval.lcp < min_length {
+                return None;
+            }
+            let (mut positions_a, mut positions_b) = (Vec::new(), Vec::new());
+            for k in interval.range {
+                match self.origin(k) {
+                    Origin::A(p) => positions_a.push(p),
+                    Origin::B(p) => positions_b.push(p),
+                    Origin::Separator => (),
+                }
+            }
+            if positions_a.is_empty() || positions_b.is_empty() {
+                None
+            } else {
+                Some(CommonSubstring {
+                    length: interval.lcp,
+                    positions_a,
+                    positions_b,
+                })
+            }
+        })
+    }
+
+    /// Enumerates maximal exact matches no shorter than `min_length`.
+    ///
+    /// A maximal exact match is a pair of equal substrings in both texts, which can be extended neither to the
+    /// left nor to the right.
+    ///
+    /// Positions of each child interval are grouped by preceding symbol, with the start of text as a group
+    /// of its own, and only groups of different children with different preceding symbols are paired,
+    /// so the pairing work is proportional to the number of reported matches.
+    pub fn maximal_exact_matches(&self, min_length: usize) -> impl Iterator<Item = Match> + '_ {
+        let min_length = Ord::max(min_length, 1);
+        LcpIntervals::new(self.lcp.as_slice())
+            .filter(move |interval| interval.lcp >= min_length)
+            .flat_map(move |interval| {
+                let mut matches = Vec::new();
+                // positions of the preceding children, grouped by preceding symbol
+                let (mut seen_a, mut seen_b) = (Groups::new(), Groups::new());
+                for child in interval.children() {
+                    let (mut child_a, mut child_b) = (Groups::new(), Groups::new());
+                    for k in child {
+                        match self.origin(k) {
+                            Origin::A(p) => child_a.entry(preceding(self.a, p)).or_default().push(p),
+                            Origin::B(p) => child_b.entry(preceding(self.b, p)).or_default().push(p),
+                            Origin::Separator => (),
+                        }
+                    }
+                    for (&x, positions_a) in child_a.iter() {
+                        for (&y, positions_b) in seen_b.iter() {
+                            if x.is_none() || x != y {
+                                push_matches(&mut matches, positions_a, positions_b, interval.lcp);
+                            }
+                        }
+                    }
+                    for (&y, positions_b) in child_b.iter() {
+                        for (&x, positions_a) in seen_a.iter() {
+                            if y.is_none() || x != y {
+                                push_matches(&mut matches, positions_a, positions_b, interval.lcp);
+                            }
+                        }
+                    }
+                    merge_groups(&mut seen_a, child_a);
+                    merge_groups(&mut seen_b, child_b);
+                }
+                matches.into_iter()
+            })
+    }
+
+    #[inline]
+    fn origin(&self, rank: usize) -> Origin {
+        let p = self.sa[rank].as_usize();
+        match p.cmp(&self.a.len()) {
+            std::cmp::Ordering::Less => Origin::A(p),
+            std::cmp::Ordering::Equal => Origin::Separator,
+            std::cmp::Ordering::Greater => Origin::B(p - self.a.len() - 1),
+        }
+    }
+}
+
+/// Positions grouped by preceding symbol, where `None` stands for the start of text.
+type Groups = BTreeMap<Option<u8>, Vec<usize>>;
+
+#[inline]
+fn preceding(t: &[u8], p: usize) -> Option<u8> {
+    p.checked_sub(1).map(|q| t[q])
+}
+
+#[inline]
+fn merge_groups(groups: &mut Groups, other: Groups) {
+    for (c, positions) in other {
+        groups.entry(c).or_default().extend(positions);
+    }
+}
+
+#[inline]
+fn push_matches(matches: &mut Vec<Match>, positions_a: &[usize], positions_b: &[usize], length: usize) {
+    for &a in positions_a {
+        matches.extend(positions_b.iter().map(|&b| Match { a, b, length }));
+    }
+}
+
+#[inline]
+fn pair(x: Origin, y: Origin) -> Option<(usize, usize)> {
+    match (x, y) {
+        (Origin::A(a), Origin::B(b)) | (Origin::B(b), Origin::A(a)) => Some((a, b)),
+        _ => None,
+    }
+}
@@ -0,0 +1,190 @@
+//! Enumeration of maximal repeats, supermaximal repeats and the longest repeated substring.
+//!
+//! Repeats are found as lcp-intervals of an [`EnhancedSuffixArray`], with left-maximality decided by the
+//! preceding symbols of suffixes, i.e. the bwt of the text in suffix array order.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::ops::Range;
+
+use crate::common::same_size;
+use crate::errors::Error;
+use crate::esa::EnhancedSuffixArray;
+use crate::index::SaIndex;
+
+/// Repeated substring, represented by its length and the range of suffix array ranks of its occurrences.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Repeat {
+    /// Length of the repeated substring.
+    pub length: usize,
+    /// Range of suffix array ranks, whose suffixes start with the repeated substring.
+    pub range: Range<usize>,
+}
+
+impl Repeat {
+    /// Number of occurrences in the text.
+    #[inline]
+    pub fn occurrences(&self) -> usize {
+        self.range.len()
+    }
+
+    /// Text positions of the occurrences, in suffix array order.
+    #[inline]
+    pub fn positions<'a, I: SaIndex>(&self, sa: &'a [I]) -> impl Iterator<Item = usize> + 'a {
+        sa[self.range.clone()].iter().map(|&p| p.as_usize())
+    }
+
+    /// Text range of the occurrence with the smallest suffix.
+    #[inline]
+    pub fn first_occurrence<I: SaIndex>(&self, sa: &[I]) -> Range<usize> {
+        let start = sa[self.range.start].as_usize();
+        start..start + self.length
+    }
+}
+
+/// Enumerates maximal repeats, i.e. repeats that can be extended neither to the left nor to the right
+/// without losing an occurrence.
+///
+/// Returns `Err(Error::IllegalArguments)` if `t` and `esa` differ in length.
+///
+/// # Arguments
+///
+/// * `t` - Input text.
+/// * `esa` - Enhanced suffix array of the text.
+/// * `min_length` - Minimum length of reported repeats, treated as at least `1`.
+/// * `min_occurrences` - Minimum number of occurrences of reported repeats, treated as at least `2`.
+///
+/// # Examples
+///
+/// ```
+/// use sais::esa::EnhancedSuffixArray;
+/// use sais::repeats::maximal_repeats;
+/// use sais::sais32::{lcp, plcp, sais};
+///
+/// let t = b"xabcyabcz";
+/// let mut sa = vec![0; t.len()];
+/// let mut plcp_array = vec![0; t.len()];
+/// let mut lcp_array = vec![0; t.len()];
+/// sais(t, &mut sa, None).unwrap();
+/// plcp(t, &sa, &mut plcp_array).unwrap();
+/// lcp(&plcp_array, &sa, &mut lcp_array).unwrap();
+///
+/// let esa = EnhancedSuffixArray::new(&sa, &lcp_array).unwrap();
+/// let repeats: Vec<_> = maximal_repeats(t, &esa, 2, 2).unwrap().collect();
+/// assert_eq!(repeats.len(), 1);
+/// assert_eq!(&t[repeats[0].first_occurrence(&sa)], b"abc");
+/// ```
+pub fn maximal_repeats<'a, T, I>(
+    t: &'a [T], esa: &EnhancedSuffixArray<'a, I>, min_length: usize, min_occurrences: usize,
+) -> Result<impl Iterator<Item = Repeat> + 'a, Error<I>>
+where
+    T: Copy + Eq,
+    I: SaIndex,
+{
+    same_size(t.len(), esa.len())?;
+    let (min_length, min_occurrences) = (Ord::max(min_length, 1), Ord::max(min_occurrences, 2));
+    let sa = esa.suffix_array();
+
+    // changes[k] counts ranks r in 1..k whose preceding symbol differs from rank r - 1, or is absent
+    let mut changes = vec![0usize; t.len() + 1];
+    for k in 1..t.len() {
+        let diverse = match (preceding(t, sa, k - 1), preceding(t, sa, k)) {
+            (Some(x), Some(y)) => x != y,
+            _ => true,
+        };
+        changes[k + 1] = changes[k] + diverse as usize;
+    }
+
+    Ok(esa.intervals().filter_map(move |interval| {
+        let left_diverse = changes[interval.range.end] > changes[interval.range.start + 1];
+        if interval.lcp >= min_length && interval.range.len() >= min_occurrences && left_diverse {
+            Some(Repeat {
+                length: interval.lcp,
+                range: interval.range,
+            })
+        } else {
+            None
+        }
+    }))
+}
+
+/// Enumerates supermaximal repeats, i.e. maximal repeats that do not occur as substring of any other maximal repeat.
+///
+/// Returns `Err(Error::IllegalArguments)` if `t` and `esa` differ in length.
+///
+/// # Arguments
+///
+/// * `t` - Input text.
+/// * `esa` - Enhanced suffix array of the text.
+/// * `min_length` - Minimum length of reported repeats, treated as at least `1`.
+/// * `min_occurrences` - Minimum number of occurrences of reported repeats, treated as at least `2`.
+pub fn supermaximal_repeats<'a, T, I>(
+    t: &'a [T], esa: &EnhancedSuffixArray<'a, I>, min_length: usize, min_occurrences: usize,
+) -> Result<impl Iterator<Item = Repeat> + 'a, Error<I>>
+where
+    T: Copy + Eq + Hash,
+    I: SaIndex,
+{
+    same_size(t.len(), esa.len())?;
+    let (min_length, min_occurrences) = (Ord::max(min_length, 1), Ord::max(min_occurrences, 2));
+    let sa = esa.suffix_array();
+
+    Ok(esa.intervals().filter_map(move |interval| {
+        if interval.lcp < min_length || interval.range.len() < min_occurrences {
+            return None;
+        }
+        // local maximum in the lcp-interval tree, whose occurrences are pairwise left distinct
+        if interval.children().any(|child| child.len() > 1) {
+            return None;
+        }
+        let mut seen = HashSet::with_capacity(interval.range.len());
+        if interval
+            .range
+            .clone()
+            .all(|k| seen.insert(preceding(t, sa, k)))
+        {
+            Some(Repeat {
+                length: interval.lcp,
+                range: interval.range,
+            })
+        } else {
+            None
+        }
+    }))
+}
+
+/// Finds the longest substring occurring at least twice in the text.
+///
+/// Returns `None` if no symbol of the text is repeated.
+///
+/// # Arguments
+///
+/// * `esa` - Enhanced suffix array of the text.
+pub fn longest_repeated_substring<I: SaIndex>(esa: &EnhancedSuffixArray<'_, I>) -> Option<Repeat> {
+    let lcp = esa.lcp_array();
+    let (rank, length) = lcp
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(k, &l)| (k, l.as_usize()))
+        .max_by_key(|&(k, l)| (l, std::cmp::Reverse(k)))?;
+    if length == 0 {
+        return None;
+    }
+    let start = (1..=rank)
+        .rev()
+        .take_while(|&k| lcp[k].as_usize() >= length)
+        .last()?
+        - 1;
+    let end = (rank..lcp.len())
+        .take_while(|&k| lcp[k].as_usize() >= length)
+        .last()?
+        + 1;
+    Some(Repeat { length, range: start..end })
+}
+
+#[inline]
+fn preceding<T: Copy, I: SaIndex>(t: &[T], sa: &[I], rank: usize) -> Option<T> {
+    sa[rank].as_usize().checked_sub(1).map(|p| t[p])
+}
@@ -0,0 +1,242 @@
+//! Range minimum queries, mostly used over lcp arrays, by stack masks of blocks or by a tree of block minima.
+
+use crate::index::SaIndex;
+
+use std::ops::Range;
+
+/// Number of values per block of [`RangeMinimum`] and [`BlockRangeMinimum`].
+const BLOCK: usize = 64;
+
+/// Range minimum query structure answering queries in constant time, after linear preprocessing.
+///
+/// Each value keeps a 64-bit mask of the minima stack in its block prefix, and a sparse table over block minima
+/// takes `(n / 64) log (n / 64)` further positions, i.e. no more than `n`.
+pub struct RangeMinimum<I: SaIndex> {
+    values: Vec<I>,
+    // bit k of masks[i] is set if values[b + k] is not greater than values[b + k..=i], where b = i / BLOCK * BLOCK
+    masks: Vec<u64>,
+    // levels[k][b] is the leftmost position of minimum in blocks b..b + 2^k
+    levels: Vec<Vec<I>>,
+}
+
+impl<I: SaIndex> RangeMinimum<I> {
+    /// Builds stack masks and sparse table of block minima over given values.
+    pub fn new(values: Vec<I>) -> Self {
+        let mut masks = Vec::with_capacity(values.len());
+        for block in values.chunks(BLOCK) {
+            let mut mask = 0u64;
+            for (k, &value) in block.iter().enumerate() {
+                while mask != 0 && block[top(mask)] > value {
+                    mask ^= 1 << top(mask);
+                }
+                mask |= 1 << k;
+                masks.push(mask);
+            }
+        }
+        let minima = (0..values.len().div_ceil(BLOCK))
+            .map(|b| {
+                let last = Ord::min((b + 1) * BLOCK, values.len()) - 1;
+                I::from_usize(b * BLOCK + masks[last].trailing_zeros() as usize)
+            })
+            .collect();
+        let mut levels: Vec<Vec<I>> = vec![minima];
+        let mut width = 2;
+        while width <= levels[0].len() {
+            let prev = &levels[levels.len() - 1];
+            let half = width / 2;
+            let level = (0..prev.len() - half)
+                .map(|b| {
+                    let (x, y) = (prev[b], prev[b + half]);
+                    if values[y.as_usize()] < values[x.as_usize()] {
+                        y
+                    } else {
+                        x
+                    }
+                })
+                .collect();
+            levels.push(level);
+            width *= 2;
+        }
+        RangeMinimum { values, masks, levels }
+    }
+
+    /// Leftmost position of minimum value in a non-empty range.
+    #[inline]
+    pub fn argmin(&self, range: Range<usize>) -> usize {
+        assert!(range.start < range.end && range.end <= self.values.len(), "invalid range minimum query");
+        let (first, last) = (range.start / BLOCK, (range.end - 1) / BLOCK);
+        if first == last {
+            return self.argmin_in_block(range);
+        }
+        let mut min = self.argmin_in_block(range.start..(first + 1) * BLOCK);
+        if first + 1 < last {
+            min = self.leftmost(min, self.argmin_of_blocks(first + 1..last));
+        }
+        self.leftmost(min, self.argmin_in_block(last * BLOCK..range.end))
+    }
+
+    /// Minimum value in a non-empty range.
+    #[inline]
+    pub fn min(&self, range: Range<usize>) -> I {
+        self.values[self.argmin(range)]
+    }
+
+    /// Leftmost position of minimum in a non-empty range within one block.
+    #[inline]
+    fn argmin_in_block(&self, range: Range<usize>) -> usize {
+        let offset = range.start % BLOCK;
+        let mask = self.masks[range.end - 1] & (u64::MAX << offset);
+        range.start - offset + mask.trailing_zeros() as usize
+    }
+
+    /// Leftmost position of minimum in a non-empty range of whole blocks.
+    #[inline]
+    fn argmin_of_blocks(&self, blocks: Range<usize>) -> usize {
+        let k = (usize::BITS - 1 - blocks.len().leading_zeros()) as usize;
+        let x = self.levels[k][blocks.start].as_usize();
+        let y = self.levels[k][blocks.end - (1 << k)].as_usize();
+        self.leftmost(x, y)
+    }
+
+    /// Position of the smaller value, or `x` for a tie where `x < y`.
+    #[inline]
+    fn leftmost(&self, x: usize, y: usize) -> usize {
+        if self.values[y] < self.values[x] {
+            y
+        } else {
+            x
+        }
+    }
+}
+
+/// Highest set bit of a non-zero mask, i.e. top of the minima stack.
+#[inline]
+fn top(mask: u64) -> usize {
+    (u64::BITS - 1 - mask.leading_zeros()) as usize
+}
+
+/// Range minimum queries over borrowed values in `O(BLOCK + log n)` time, with a tree of block minima taking
+/// `2n / BLOCK` values of space. Also finds the nearest values satisfying a bound, e.g. smaller values.
+pub struct BlockRangeMinimum<'a, I: SaIndex> {
+    values: &'a [I],
+    leaves: usize,
+    // heap ordered minima, where tree[leaves + b] is the minimum of block b
+    tree: Vec<I>,
+}
+
+impl<'a, I: SaIndex> BlockRangeMinimum<'a, I> {
+    /// Builds tree of block minima over given values.
+    pub fn new(values: &'a [I]) -> Self {
+        let leaves = values.len().div_ceil(BLOCK).next_power_of_two();
+        let mut tree = vec![I::from_usize(I::MAX_LENGTH); 2 * leaves];
+        for (b, block) in values.chunks(BLOCK).enumerate() {
+            tree[leaves + b] = block.iter().copied().fold(tree[leaves + b], Ord::min);
+        }
+        for node in (1..leaves).rev() {
+            tree[node] = Ord::min(tree[2 * node], tree[2 * node + 1]);
+        }
+        BlockRangeMinimum { values, leaves, tree }
+    }
+
+    /// Leftmost position of minimum value in a non-empty range.
+    pub fn argmin(&self, range: Range<usize>) -> usize {
+        assert!(range.start < range.end && range.end <= self.values.len(), "invalid range minimum query");
+        let (first, last) = (range.start.div_ceil(BLOCK), range.end / BLOCK);
+        let min = if first < last {
+            let mut min = self.blocks_min(first..last);
+            for i in (range.start..first * BLOCK).chain(last * BLOCK..range.end) {
+                min = Ord::min(min, self.values[i]);
+            }
+            min
+        } else {
+            self.values[range.clone()]
+                .iter()
+                .copied()
+                .fold(self.values[range.start], Ord::min)
+        };
+        self.find_first(range, |v| v <= min)
+            .expect("minimum is in range")
+    }
+
+    /// Minimum value in a non-empty range.
+    #[inline]
+    pub fn min(&self, range: Range<usize>) -> I {
+        self.values[self.argmin(range)]
+    }
+
+    /// Leftmost position in `range` whose value satisfies `bound`, which must hold for the minimum of any values
+    /// it holds for, e.g. `|v| v < x`.
+    pub fn find_first(&self, range: Range<usize>, bound: impl Fn(I) -> bool) -> Option<usize> {
+        let scan = |r: Range<usize>| r.into_iter().find(|&i| bound(self.values[i]));
+        let (first, last) = (range.start.div_ceil(BLOCK), range.end / BLOCK);
+        if first >= last {
+            return scan(range);
+        }
+        scan(range.start..first * BLOCK)
+            .or_else(|| {
+                let b = self.first_block(1, 0..self.leaves, &(first..last), &bound)?;
+                scan(b * BLOCK..(b + 1) * BLOCK)
+            })
+            .or_else(|| scan(last * BLOCK..range.end))
+    }
+
+    /// Rightmost position in `range` whose value satisfies `bound`, see [`find_first`](Self::find_first).
+    pub fn find_last(&self, range: Range<usize>, bound: impl Fn(I) -> bool) -> Option<usize> {
+        let scan = |r: Range<usize>| r.into_iter().rev().find(|&i| bound(self.values[i]));
+        let (first, last) = (range.start.div_ceil(BLOCK), range.end / BLOCK);
+        if first >= last {
+            return scan(range);
+        }
+        scan(last * BLOCK..range.end)
+            .or_else(|| {
+                let b = self.last_block(1, 0..self.leaves, &(first..last), &bound)?;
+                scan(b * BLOCK..(b + 1) * BLOCK)
+            })
+            .or_else(|| scan(range.start..first * BLOCK))
+    }
+
+    /// Minimum of a non-empty range of blocks.
+    fn blocks_min(&self, blocks: Range<usize>) -> I {
+        let (mut lo, mut hi) = (blocks.start + self.leaves, blocks.end + self.leaves);
+        let mut min = self.tree[lo];
+        while lo < hi {
+            if lo & 1 == 1 {
+                min = Ord::min(min, self.tree[lo]);
+                lo += 1;
+            }
+            if hi & 1 == 1 {
+                hi -= 1;
+                min = Ord::min(min, self.tree[hi]);
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+        min
+    }
+
+    /// Leftmost block in `blocks` below `node` spanning `span` whose minimum satisfies `bound`.
+    fn first_block(&self, node: usize, span: Range<usize>, blocks: &Range<usize>, bound: &impl Fn(I) -> bool) -> Option<usize> {
+        if span.end <= blocks.start || blocks.end <= span.start || !bound(self.tree[node]) {
+            return None;
+        }
+        if span.len() == 1 {
+            return Some(span.start);
+        }
+        let mid = (span.start + span.end) / 2;
+        self.first_block(2 * node, span.start..mid, blocks, bound)
+            .or_else(|| self.first_block(2 * node + 1, mid..span.end, blocks, bound))
+    }
+
+    /// Rightmost block in `blocks` below `node` spanning `span` whose minimum satisfies `bound`.
+    fn last_block(&self, node: usize, span: Range<usize>, blocks: &Range<usize>, bound: &impl Fn(I) -> bool) -> Option<usize> {
+        if span.end <= blocks.start || blocks.end <= span.start || !bound(self.tree[node]) {
+            return None;
+        }
+        if span.len() == 1 {
+            return Some(span.start);
+        }
+        let mid = (span.start + span.end) / 2;
+        self.last_block(2 * node + 1, mid..span.end, blocks, bound)
+            .or_else(|| self.last_block(2 * node, span.start..mid, blocks, bound))
+    }
+}
@@ -0,0 +1,369 @@
+//! Rotation sorting helpers shared by cyclic and bijective bwt variants.
+
+use std::cmp::Ordering;
+#[cfg(any(feature = "sais16", feature = "sais32"))]
+use std::ops::Range;
+
+#[cfg(any(feature = "sais16", feature = "sais32"))]
+use crate::common::max_size;
+use crate::common::same_size;
+use crate::errors::Error;
+use crate::index::SaIndex;
+
+/// Computes cyclic bwt of `t` into `u`, returns row index of the original rotation.
+///
+/// The least rotation of `t` is a power `w^k` of a lyndon word `w`, whose suffixes are sorted the same way
+/// as its rotations, so `suffix_array` constructs the suffix array of `w` only, without doubling the text.
+/// Each rotation of `w` stands for `k` equal rotations of `t`, the original one is reported as the first of them.
+pub fn bwt_cyclic<T, I, F>(t: &[T], u: &mut [T], suffix_array: F) -> Result<usize, Error<I>>
+where
+    T: Copy + Ord,
+    I: SaIndex,
+    F: FnOnce(&[T], &mut [I]) -> Result<(), Error<I>>,
+{
+    let n = same_size(t.len(), u.len())?;
+    if n == 0 {
+        return Ok(0);
+    }
+    let shift = least_rotation(t);
+    let w: Vec<T> = (0..n)
+        .map(|x| t[(shift + x) % n])
+        .take(lyndon_root_length(&t[shift..], &t[..shift]))
+        .collect();
+    let (m, k) = (w.len(), n / w.len());
+    let mut sa = vec![I::default(); m];
+    suffix_array(&w, &mut sa)?;
+
+    let original = (n - shift) % m;
+    let mut primary = 0;
+    for (row, p) in sa.iter().map(|&p| p.as_usize()).enumerate() {
+        if p == original {
+            primary = row * k;
+        }
+        u[row * k..(row + 1) * k].fill(w[(p + m - 1) % m]);
+    }
+    Ok(primary)
+}
+
+/// Finds start of the least rotation by comparing two candidate rotations at a time.
+fn least_rotation<T: Ord>(t: &[T]) -> usize {
+    let n = t.len();
+    let (mut i, mut j, mut k) = (0, 1, 0);
+    while i < n && j < n && k < n {
+        match t[(i + k) % n].cmp(&t[(j + k) % n]) {
+            Ordering::Equal => k += 1,
+            Ordering::Greater => {
+                i += k + 1;
+                k = 0;
+            }
+            Ordering::Less => {
+                j += k + 1;
+                k = 0;
+            }
+        }
+        if i == j {
+            j += 1;
+        }
+    }
+    Ord::min(i, j)
+}
+
+/// Length of the lyndon word `w` for the least rotation `head ++ tail` equal to `w^k`, by duval algorithm.
+fn lyndon_root_length<T: Ord>(head: &[T], tail: &[T]) -> usize {
+    let at = |x: usize| if x < head.len() { &head[x] } else { &tail[x - head.len()] };
+    let n = head.len() + tail.len();
+    let (mut j, mut k) = (1, 0);
+    while j < n && at(k) <= at(j) {
+        k = if at(k) < at(j) { 0 } else { k + 1 };
+        j += 1;
+    }
+    debug_assert!(j == n && n.is_multiple_of(j - k));
+    j - k
+}
+
+/// Inverts cyclic bwt by walking the rotations forward from the original one.
+pub fn unbwt_cyclic<T: Copy + Into<usize>>(t: &[T], u: &mut [T], primary: usize) {
+    let next = standard_permutation(t);
+    let mut row = primary;
+    for x in u.iter_mut() {
+        row = next[row];
+        *x = t[row];
+    }
+}
+
+/// Splits text into lyndon factors by duval algorithm, returns starting positions of the factors.
+#[cfg(any(feature = "sais16", feature = "sais32"))]
+pub fn lyndon_factor_starts<T: Ord>(t: &[T]) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut i = 0;
+    while i < t.len() {
+        let (mut j, mut k) = (i + 1, i);
+        while j < t.len() && t[k] <= t[j] {
+            k = if t[k] < t[j] { i } else { k + 1 };
+            j += 1;
+        }
+        while i <= k {
+            starts.push(i);
+            i += j - k;
+        }
+    }
+    starts
+}
+
+/// Computes bijective bwt, i.e. last symbols of rotations of all lyndon factors sorted in omega order.
+///
+/// Rotations are sorted in linear time by induced sorting, where every position is typed against its
+/// cyclic successor within its lyndon factor, see [`sort_rotations`]. Besides the output, it takes about
+/// three arrays of `n` indices and `4n` bytes of flags and names, halving in each recursion level.
+#[cfg(any(feature = "sais16", feature = "sais32"))]
+pub fn bbwt<T, I>(t: &[T], u: &mut [T]) -> Result<(), Error<I>>
+where
+    T: Copy + Ord + Into<usize>,
+    I: SaIndex,
+{
+    let n = max_size(same_size(t.len(), u.len())?, I::MAX_LENGTH)?;
+    let starts = lyndon_factor_starts(t);
+    let factors = Factors::new(n, starts.iter().copied());
+    let text: Vec<I> = t.iter().map(|&c| I::from_usize(c.into())).collect();
+    let sigma = t.iter().map(|&c| c.into() + 1).max().unwrap_or(0);
+
+    let mut sa = vec![I::default(); n];
+    sort_rotations(&text, sigma, &factors, &mut sa);
+    for (c, &p) in u.iter_mut().zip(sa.iter()) {
+        *c = t[factors.prev(p.as_usize())];
+    }
+    Ok(())
+}
+
+/// Lyndon factors of a text, where each factor is read cyclically.
+#[cfg(any(feature = "sais16", feature = "sais32"))]
+struct Factors<I> {
+    is_start: Vec<bool>,
+    // last position of the factor at its start, and start at its last position
+    link: Vec<I>,
+}
+
+#[cfg(any(feature = "sais16", feature = "sais32"))]
+impl<I: SaIndex> Factors<I> {
+    fn new(n: usize, starts: impl Iterator<Item = usize>) -> Self {
+        let mut is_start = vec![false; n];
+        let mut link = vec![I::default(); n];
+        let mut starts = starts.peekable();
+        while let Some(start) = starts.next() {
+            let last = starts.peek().copied().unwrap_or(n) - 1;
+            is_start[start] = true;
+            link[start] = I::from_usize(last);
+            link[last] = I::from_usize(start);
+        }
+        Factors { is_start, link }
+    }
+
+    #[inline]
+    fn next(&self, i: usize) -> usize {
+        if i + 1 < self.is_start.len() && !self.is_start[i + 1] {
+            i + 1
+        } else {
+            self.link[i].as_usize()
+        }
+    }
+
+    #[inline]
+    fn prev(&self, i: usize) -> usize {
+        if self.is_start[i] {
+            self.link[i].as_usize()
+        } else {
+            i - 1
+        }
+    }
+
+    /// Whether `i` is a factor of length one, whose only rotation is a run of a single symbol.
+    #[inline]
+    fn is_single(&self, i: usize) -> bool {
+        self.is_start[i] && self.link[i].as_usize() == i
+    }
+}
+
+/// Sorts rotations of lyndon factors of `t` over alphabet `0..sigma` in omega order into `sa`.
+///
+/// This is sais with cyclic successors: a position is s-type if its rotation is smaller than the rotation
+/// of its successor, and every factor start is lms since a lyndon word is smaller than its other rotations.
+/// Lms-substrings are sorted by induction, named and sorted recursively as factors of the reduced text,
+/// which are again lyndon words. A factor of length one `c` sorts between l-type and s-type rotations
+/// in the bucket of `c`, and equal factors give equal rotations, which do not affect the bwt.
+#[cfg(any(feature = "sais16", feature = "sais32"))]
+fn sort_rotations<I: SaIndex>(t: &[I], sigma: usize, factors: &Factors<I>, sa: &mut [I]) {
+    let n = t.len();
+    let empty = I::from_usize(I::MAX_LENGTH);
+
+    // types, computed backwards from the start of each factor
+    let mut stype = vec![false; n];
+    for last in (0..n).rev().filter(|&i| factors.next(i) <= i) {
+        let start = factors.next(last);
+        stype[start] = start != last;
+        for i in (start + 1..=last).rev() {
+            let j = factors.next(i);
+            stype[i] = t[i] < t[j] || (t[i] == t[j] && stype[j]);
+        }
+    }
+    let is_lms = |i: usize| stype[i] && !stype[factors.prev(i)];
+
+    // bucket starts, each bucket holds l-type, single and s-type rotations in order
+    let mut counts = vec![[0usize; 3]; sigma];
+    for i in 0..n {
+        let kind = if factors.is_single(i) {
+            1
+        } else if stype[i] {
+            2
+        } else {
+            0
+        };
+        counts[t[i].as_usize()][kind] += 1;
+    }
+    let mut bucket_starts = Vec::with_capacity(sigma + 1);
+    bucket_starts.push(0);
+    for c in counts.iter() {
+        bucket_starts.push(bucket_starts.last().unwrap() + c.iter().sum::<usize>());
+    }
+
+    let induce = |sa: &mut [I]| {
+        let mut heads: Vec<usize> = bucket_starts[..sigma].to_vec();
+        for k in 0..n {
+            if sa[k] != empty {
+                let j = factors.prev(sa[k].as_usize());
+                if !stype[j] && !factors.is_single(j) {
+                    let c = t[j].as_usize();
+                    sa[heads[c]] = I::from_usize(j);
+                    heads[c] += 1;
+                }
+            }
+        }
+        let mut tails: Vec<usize> = bucket_starts[1..].to_vec();
+        for k in (0..n).rev() {
+            if sa[k] != empty {
+                let j = factors.prev(sa[k].as_usize());
+                if stype[j] {
+                    let c = t[j].as_usize();
+                    tails[c] -= 1;
+                    sa[tails[c]] = I::from_usize(j);
+                }
+            }
+        }
+    };
+    let place = |sa: &mut [I], lms: &mut dyn Iterator<Item = usize>| {
+        sa.fill(empty);
+        let mut tails: Vec<usize> = bucket_starts[1..].to_vec();
+        for p in lms {
+            let c = t[p].as_usize();
+            tails[c] -= 1;
+            sa[tails[c]] = I::from_usize(p);
+        }
+        let mut singles: Vec<usize> = (0..sigma)
+            .map(|c| bucket_starts[c] + counts[c][0])
+            .collect();
+        for (p, &c) in t.iter().enumerate().filter(|&(p, _)| factors.is_single(p)) {
+            let c = c.as_usize();
+            sa[singles[c]] = I::from_usize(p);
+            singles[c] += 1;
+        }
+    };
+
+    // sort and name lms-substrings
+    let lms: Vec<usize> = (0..n).filter(|&i| is_lms(i)).collect();
+    place(sa, &mut lms.iter().copied());
+    induce(sa);
+    let sorted: Vec<usize> = sa
+        .iter()
+        .map(|&p| p.as_usize())
+        .filter(|&p| p < n && is_lms(p))
+        .collect();
+    let same_substring = |mut x: usize, mut y: usize| loop {
+        if t[x] != t[y] || stype[x] != stype[y] {
+            return false;
+        }
+        (x, y) = (factors.next(x), factors.next(y));
+        match (is_lms(x), is_lms(y)) {
+            (true, true) => return t[x] == t[y],
+            (false, false) => continue,
+            _ => return false,
+        }
+    };
+    // lms positions are at least two apart, so halves of positions are unique slots for names
+    let mut names = vec![empty; n / 2 + 1];
+    let mut name = 0;
+    for (k, &p) in sorted.iter().enumerate() {
+        if k > 0 && !same_substring(sorted[k - 1], p) {
+            name += 1;
+        }
+        names[p / 2] = I::from_usize(name);
+    }
+
+    // sort lms positions by rotations of the reduced text, whose factors start at factor starts
+    let reduced: Vec<I> = lms.iter().map(|&p| names[p / 2]).collect();
+    drop(names);
+    let mut reduced_sa = vec![I::default(); lms.len()];
+    if name + 1 >= lms.len() {
+        for (k, &c) in reduced.iter().enumerate() {
+            reduced_sa[c.as_usize()] = I::from_usize(k);
+        }
+    } else {
+        let reduced_starts = (0..lms.len()).filter(|&k| factors.is_start[lms[k]]);
+        let reduced_factors = Factors::new(lms.len(), reduced_starts);
+        sort_rotations(&reduced, name + 1, &reduced_factors, &mut reduced_sa);
+    }
+
+    place(sa, &mut reduced_sa.iter().rev().map(|&k| lms[k.as_usize()]));
+    induce(sa);
+}
+
+/// Inverts bijective bwt by decomposing the standard permutation into cycles, each of which is a lyndon factor.
+#[cfg(any(feature = "sais16", feature = "sais32"))]
+pub fn unbbwt<T: Copy + Into<usize>>(t: &[T], u: &mut [T]) {
+    let n = t.len();
+    let next = standard_permutation(t);
+
+    // rows in ascending order start cycles of lyndon factors in ascending order
+    let mut visited = vec![false; n];
+    let mut factors: Vec<Range<usize>> = Vec::new();
+    let mut symbols = Vec::with_capacity(n);
+    for first in 0..n {
+        if visited[first] {
+            continue;
+        }
+        let start = symbols.len();
+        let mut row = first;
+        loop {
+            visited[row] = true;
+            row = next[row];
+            symbols.push(t[row]);
+            if row == first {
+                break;
+            }
+        }
+        factors.push(start..symbols.len());
+    }
+
+    let mut position = 0;
+    for factor in factors.into_iter().rev() {
+        u[position..position + factor.len()].copy_from_slice(&symbols[factor.clone()]);
+        position += factor.len();
+    }
+}
+
+/// Row of each rotation shifted left by one symbol, i.e. stable sorting positions of the last column.
+fn standard_permutation<T: Copy + Into<usize>>(t: &[T]) -> Vec<usize> {
+    let sigma = t.iter().map(|&c| c.into() + 1).max().unwrap_or(0);
+    let mut buckets = vec![0usize; sigma + 1];
+    for &c in t {
+        buckets[c.into() + 1] += 1;
+    }
+    for c in 0..sigma {
+        buckets[c + 1] += buckets[c];
+    }
+
+    let mut next = vec![0usize; t.len()];
+    for (i, &c) in t.iter().enumerate() {
+        next[buckets[c.into()]] = i;
+        buckets[c.into()] += 1;
+    }
+    next
+}
@@ -350,6 +350,53 @@ pub fn lcp(plcp: &[i32], sa: &[i32], lcp: &mut [i32]) -> Result<()> {
     }
 }
 
+pub fn bwt_cyclic(t: &[u16], u: &mut [u16]) -> Result<i32> {
+    max_size(t.len(), MAX_LENGTH)?;
+    crate::rotation::bwt_cyclic(t, u, |w, sa| sais(w, sa, None)).map(|i| i as i32)
+}
+
+pub fn unbwt_cyclic(t: &[u16], u: &mut [u16], i: i32) -> Result<()> {
+    let n = same_size(t.len(), u.len())?;
+    if i < 0 || i as usize >= Ord::max(n, 1) {
+        Err(Error::IllegalArguments)?
+    }
+    crate::rotation::unbwt_cyclic(t, u, i as usize);
+    Ok(())
+}
+
+pub fn bbwt(t: &[u16], u: &mut [u16]) -> Result<()> {
+    crate::rotation::bbwt(t, u)
+}
+
+pub fn unbbwt(t: &[u16], u: &mut [u16]) -> Result<()> {
+    same_size(t.len(), u.len())?;
+    crate::rotation::unbbwt(t, u);
+    Ok(())
+}
+
+pub fn lyndon_array(t: &[u16], sa: &mut [i32], lyndon: &mut [i32]) -> Result<()> {
+    sais(t, sa, None)?;
+    crate::lyndon::lyndon_array(&sa[..t.len()], lyndon)
+}
+
+pub fn bwt_from_sa(t: &[u16], sa: &[i32], u: &mut [u16]) -> Result<i32> {
+    crate::common::bwt_from_sa(t, sa, u, None).map(|i| i as i32)
+}
+
+pub fn bwt_aux_from_sa(t: &[u16], sa: &[i32], u: &mut [u16], i: &mut [i32]) -> Result<()> {
+    let r = aux_rate(i.len(), t.len())?;
+    crate::common::bwt_from_sa(t, sa, u, Some((i, r))).map(|_| ())
+}
+
+pub fn sa_from_bwt(t: &[u16], sa: &mut [i32], i: i32) -> Result<()> {
+    crate::common::sa_from_bwt(t, sa, i)
+}
+
+pub fn sa_and_bwt(t: &[u16], sa: &mut [i32], u: &mut [u16], freq: Option<&mut [i32]>) -> Result<i32> {
+    sais(t, sa, freq)?;
+    bwt_from_sa(t, &sa[..t.len()], u)
+}
+
 #[cfg(feature = "parallel")]
 pub mod parallel {
     //! Multi-threaded 32-bit sais algorithms on u16 array inputs.
@@ -503,4 +550,14 @@ pub mod parallel {
             interpret_return_code_32(code).map(|_| ())
         }
     }
+
+    pub fn lyndon_array(t: &[u16], sa: &mut [i32], lyndon: &mut [i32], threads: i32) -> Result<()> {
+        sais(t, sa, None, threads)?;
+        crate::lyndon::lyndon_array(&sa[..t.len()], lyndon)
+    }
+
+    pub fn sa_and_bwt(t: &[u16], sa: &mut [i32], u: &mut [u16], freq: Option<&mut [i32]>, threads: i32) -> Result<i32> {
+        sais(t, sa, freq, threads)?;
+        bwt_from_sa(t, &sa[..t.len()], u)
+    }
 }
@@ -0,0 +1,173 @@
+//! Conversions between libsais bwt layout and the sentinel-included bwt of textbooks, BWA and sdsl.
+//!
+//! Bwt computed by libsais omits the sentinel `$` and reports its row as the primary index, i.e. the
+//! sentinel-included bwt of length `n + 1` is the libsais bwt with `$` inserted at the primary index.
+//! How `$` is represented is chosen by a [`SentinelEncoding`].
+
+use crate::common::{max_size, same_size};
+use crate::errors::Error;
+use crate::index::SaIndex;
+
+/// Representation of text symbols and the sentinel in a sentinel-included bwt.
+pub trait SentinelEncoding<T> {
+    /// Symbol type of the sentinel-included bwt.
+    type Symbol: Copy + Eq;
+
+    /// Encoded sentinel.
+    fn sentinel(&self) -> Self::Symbol;
+
+    /// Encodes a text symbol, returns `None` if it is not representable.
+    fn encode(&self, c: T) -> Option<Self::Symbol>;
+
+    /// Decodes a text symbol, returns `None` if it is not an encoded text symbol, e.g. the sentinel.
+    fn decode(&self, s: Self::Symbol) -> Option<T>;
+}
+
+/// Widened symbol type, with sentinel `0` and symbol `c` encoded as `c + 1`, e.g. `u8` into `u16`.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Widened;
+
+impl SentinelEncoding<u8> for Widened {
+    type Symbol = u16;
+
+    #[inline]
+    fn sentinel(&self) -> u16 {
+        0
+    }
+
+    #[inline]
+    fn encode(&self, c: u8) -> Option<u16> {
+        Some(c as u16 + 1)
+    }
+
+    #[inline]
+    fn decode(&self, s: u16) -> Option<u8> {
+        s.checked_sub(1).and_then(|c| c.try_into().ok())
+    }
+}
+
+impl SentinelEncoding<u16> for Widened {
+    type Symbol = u32;
+
+    #[inline]
+    fn sentinel(&self) -> u32 {
+        0
+    }
+
+    #[inline]
+    fn encode(&self, c: u16) -> Option<u32> {
+        Some(c as u32 + 1)
+    }
+
+    #[inline]
+    fn decode(&self, s: u32) -> Option<u16> {
+        s.checked_sub(1).and_then(|c| c.try_into().ok())
+    }
+}
+
+/// Reserved symbol as the sentinel, e.g. `b'$'`, which must not occur in the text.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Reserved<T>(pub T);
+
+impl<T: Copy + Eq> SentinelEncoding<T> for Reserved<T> {
+    type Symbol = T;
+
+    #[inline]
+    fn sentinel(&self) -> T {
+        self.0
+    }
+
+    #[inline]
+    fn encode(&self, c: T) -> Option<T> {
+        (c != self.0).then_some(c)
+    }
+
+    #[inline]
+    fn decode(&self, s: T) -> Option<T> {
+        (s != self.0).then_some(s)
+    }
+}
+
+/// Converts libsais bwt `u` with primary index `i` into the sentinel-included bwt.
+///
+/// Returns `Err(Error::IllegalArguments)` if `bwt.len() != u.len() + 1`, `i` is not a valid primary index,
+/// or `u` contains symbols not representable by `encoding`.
+///
+/// # Examples
+///
+/// ```
+/// use sais::sais32::bwt;
+/// use sais::sentinel::{with_sentinel, without_sentinel, Reserved};
+///
+/// let t = b"banana";
+/// let mut u = vec![0; t.len()];
+/// let mut a = vec![0; t.len()];
+/// let i = bwt(t, &mut u, &mut a, None).unwrap();
+///
+/// let mut bwt_with_sentinel = vec![0; t.len() + 1];
+/// with_sentinel(&u, i, &Reserved(b'$'), &mut bwt_with_sentinel).unwrap();
+/// assert_eq!(bwt_with_sentinel, b"annb$aa");
+///
+/// let mut v = vec![0; t.len()];
+/// assert_eq!(without_sentinel(&bwt_with_sentinel, &Reserved(b'$'), &mut v), Ok(i));
+/// assert_eq!(v, u);
+/// ```
+pub fn with_sentinel<T, I, E>(u: &[T], i: I, encoding: &E, bwt: &mut [E::Symbol]) -> Result<(), Error<I>>
+where
+    T: Copy,
+    I: SaIndex,
+    E: SentinelEncoding<T>,
+{
+    let n = max_size(same_size(u.len() + 1, bwt.len())? - 1, I::MAX_LENGTH - 1)?;
+    let valid = if n == 0 {
+        i == I::default()
+    } else {
+        i >= I::from_usize(1) && i.as_usize() <= n
+    };
+    if !valid {
+        Err(Error::IllegalArguments)?
+    }
+
+    let i = i.as_usize();
+    for (row, s) in bwt.iter_mut().enumerate() {
+        *s = match row {
+            row if row == i => encoding.sentinel(),
+            row => encoding
+                .encode(u[if row < i { row } else { row - 1 }])
+                .ok_or(Error::IllegalArguments)?,
+        };
+    }
+    Ok(())
+}
+
+/// Converts sentinel-included `bwt` into libsais bwt `u`, returns the primary index for `unbwt`.
+///
+/// Returns `Err(Error::IllegalArguments)` if `bwt.len() != u.len() + 1`, `bwt` does not contain
+/// exactly one sentinel, or contains symbols not decodable by `encoding`.
+pub fn without_sentinel<T, I, E>(bwt: &[E::Symbol], encoding: &E, u: &mut [T]) -> Result<I, Error<I>>
+where
+    T: Copy,
+    I: SaIndex,
+    E: SentinelEncoding<T>,
+{
+    let n = max_size(same_size(u.len() + 1, bwt.len())? - 1, I::MAX_LENGTH - 1)?;
+
+    let mut primary = None;
+    let mut symbols = u.iter_mut();
+    for (row, &s) in bwt.iter().enumerate() {
+        if s == encoding.sentinel() {
+            if primary.replace(row).is_some() {
+                Err(Error::IllegalArguments)?
+            }
+        } else {
+            let c = encoding.decode(s).ok_or(Error::IllegalArguments)?;
+            *symbols.next().ok_or(Error::IllegalArguments)? = c;
+        }
+    }
+    match primary {
+        // only the sentinel suffix itself ends with the sentinel, in row 0 iff the text is empty
+        Some(0) if n > 0 => Err(Error::IllegalArguments),
+        Some(primary) => Ok(I::from_usize(primary)),
+        None => Err(Error::IllegalArguments),
+    }
+}
@@ -0,0 +1,175 @@
+//! Sparse suffix array over selected positions of a text, e.g. word starts.
+//!
+//! The full suffix array is constructed by [`sais`] and filtered to sampled suffixes,
+//! which keeps their order, so only the construction needs memory for all suffixes.
+//! The sparse lcp array is the minimum of full lcp values between consecutive sampled suffixes, which are
+//! read from the permuted lcp array while filtering, so the full lcp array is never materialized.
+//!
+//! Construction of a text of `n` bytes peaks at `5n` bytes for the full suffix array and sample flags,
+//! plus `4n` bytes for the permuted lcp array with lcp, before shrinking to `4m` or `8m` bytes
+//! for `m` sampled suffixes. Kasai algorithm restricted to sampled positions would avoid the permuted
+//! lcp array only for evenly spaced samples, its bound on consecutive lcp values does not hold otherwise.
+
+use std::ops::Range;
+
+use crate::common::{max_size, pattern_range};
+use crate::errors::Error;
+use crate::sais32::{plcp, sais, Result, MAX_LENGTH};
+
+/// Suffix array of a text restricted to sampled suffixes, with optional lcp array.
+///
+/// Deserialization borrows the text from the input, which needs a format with borrowed bytes such as bincode,
+/// and checks that sampled suffixes are distinct positions of the text but not their order.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SparseSuffixArrayParts<'a>"))]
+pub struct SparseSuffixArray<'a> {
+    text: &'a [u8],
+    sa: Vec<i32>,
+    lcp: Option<Vec<i32>>,
+}
+
+/// Fields of a deserialized sparse suffix array, before validation.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SparseSuffixArrayParts<'a> {
+    text: &'a [u8],
+    sa: Vec<i32>,
+    lcp: Option<Vec<i32>>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> TryFrom<SparseSuffixArrayParts<'a>> for SparseSuffixArray<'a> {
+    type Error = &'static str;
+
+    fn try_from(parts: SparseSuffixArrayParts<'a>) -> std::result::Result<Self, Self::Error> {
+        let SparseSuffixArrayParts { text, sa, lcp } = parts;
+        let mut seen = vec![false; text.len()];
+        for &p in sa.iter() {
+            match usize::try_from(p) {
+                Ok(p) if p < text.len() && !seen[p] => seen[p] = true,
+                _ => return Err("sampled suffixes not distinct positions of the text"),
+            }
+        }
+        if let Some(lcp) = lcp.as_deref() {
+            if lcp.len() != sa.len() || lcp.first().is_some_and(|&l| l != 0) || lcp.iter().any(|&l| l < 0) {
+                return Err("lcp array inconsistent with sampled suffixes");
+            }
+        }
+        Ok(SparseSuffixArray { text, sa, lcp })
+    }
+}
+
+impl<'a> SparseSuffixArray<'a> {
+    /// Constructs sparse suffix array of suffixes starting at `positions`, in any order with duplicates allowed.
+    ///
+    /// Returns `Err(Error::IllegalArguments)` if any position is out of text bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Input text.
+    /// * `positions` - Starting positions of sampled suffixes.
+    /// * `with_lcp` - Whether to compute the sparse lcp array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sais::sparse::SparseSuffixArray;
+    ///
+    /// let text = b"to be or not to be";
+    /// let ssa = SparseSuffixArray::new(text, &[0, 3, 6, 9, 13, 16], true).unwrap();
+    /// assert_eq!(ssa.suffix_array(), [16, 3, 9, 6, 13, 0]);
+    /// assert_eq!(ssa.lcp_array(), Some(&[0, 2, 0, 0, 0, 5][..]));
+    /// assert_eq!(ssa.count(b"to be"), 2);
+    /// ```
+    pub fn new(text: &'a [u8], positions: &[usize], with_lcp: bool) -> Result<Self> {
+        let mut sampled = vec![false; text.len()];
+        for &p in positions {
+            *sampled.get_mut(p).ok_or(Error::IllegalArguments)? = true;
+        }
+        Self::build(text, sampled, with_lcp)
+    }
+
+    /// Constructs sparse suffix array of suffixes starting at positions satisfying `predicate`.
+    pub fn from_predicate<F: FnMut(usize) -> bool>(text: &'a [u8], predicate: F, with_lcp: bool) -> Result<Self> {
+        Self::build(text, (0..text.len()).map(predicate).collect(), with_lcp)
+    }
+
+    fn build(text: &'a [u8], sampled: Vec<bool>, with_lcp: bool) -> Result<Self> {
+        let n = max_size(text.len(), MAX_LENGTH)?;
+        let mut sa = vec![0; n];
+        sais(text, &mut sa, None)?;
+
+        let lcp = if with_lcp {
+            let mut plcp_array = vec![0; n];
+            plcp(text, &sa, &mut plcp_array)?;
+
+            // minimum of full lcp values since the previous sampled suffix, compacting sampled suffixes in place
+            let mut sparse = Vec::new();
+            let mut common = 0;
+            for k in 0..n {
+                let p = sa[k];
+                common = Ord::min(common, plcp_array[p as usize]);
+                if sampled[p as usize] {
+                    sa[sparse.len()] = p;
+                    sparse.push(if sparse.is_empty() { 0 } else { common });
+                    common = i32::MAX;
+                }
+            }
+            sa.truncate(sparse.len());
+            sparse.shrink_to_fit();
+            Some(sparse)
+        } else {
+            sa.retain(|&p| sampled[p as usize]);
+            None
+        };
+        sa.shrink_to_fit();
+        Ok(SparseSuffixArray { text, sa, lcp })
+    }
+
+    /// Indexed text.
+    #[inline]
+    pub fn text(&self) -> &'a [u8] {
+        self.text
+    }
+
+    /// Sampled suffixes in lexicographical order.
+    #[inline]
+    pub fn suffix_array(&self) -> &[i32] {
+        &self.sa
+    }
+
+    /// Lcp array of sampled suffixes, `lcp[0] == 0`, if it was computed on construction.
+    #[inline]
+    pub fn lcp_array(&self) -> Option<&[i32]> {
+        self.lcp.as_deref()
+    }
+
+    /// Number of sampled suffixes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sa.len()
+    }
+
+    /// Whether no suffix is sampled.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sa.is_empty()
+    }
+
+    /// Rows of the sparse suffix array whose suffixes start with `pattern`.
+    #[inline]
+    pub fn range(&self, pattern: &[u8]) -> Range<usize> {
+        pattern_range(self.text, &self.sa, pattern)
+    }
+
+    /// Number of occurrences of `pattern` at sampled positions.
+    #[inline]
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        self.range(pattern).len()
+    }
+
+    /// Occurrences of `pattern` at sampled positions, in suffix array order.
+    pub fn find(&self, pattern: &[u8]) -> impl Iterator<Item = usize> + '_ {
+        self.sa[self.range(pattern)].iter().map(|&p| p as usize)
+    }
+}
@@ -0,0 +1,237 @@
+//! Streaming bwt encoder and decoder over [`Write`] and [`Read`].
+//!
+//! A stream is split into blocks, each of which is transformed independently by
+//! [`bwt`](crate::sais32::SaisContext::bwt), so that memory usage is bounded by the block size.
+//! All integers in the framed format are little endian:
+//!
+//! ```text
+//! header  := magic "SBWT", block_size: u32
+//! block   := length: u32 (> 0), primary: u32, bwt: [u8; length]
+//! trailer := 0: u32
+//! ```
+
+use std::io::{self, Read, Write};
+
+use crate::compress::{read_frame_start, read_header, write_frame_start, write_header, write_u32};
+use crate::errors::Error;
+use crate::sais32::{SaisContext, UnbwtContext, MAX_LENGTH};
+
+/// Magic bytes of the stream header.
+pub const MAGIC: &[u8; 4] = b"SBWT";
+
+/// Default block size.
+pub const DEFAULT_BLOCK_SIZE: usize = 1 << 20;
+
+fn sais_error(e: Error<i32>) -> io::Error {
+    io::Error::other(e)
+}
+
+/// Bwt encoder writing framed blocks into the underlying writer.
+///
+/// Pending input is written as a block once the block is full and more input arrives, on [`flush`](Write::flush)
+/// and on [`finish`](BwtWriter::finish), so that a failed write of a block never consumes input. Every flush of a partial block emits a short block, which compresses
+/// worse than a full one, so flush only where the reader needs the data.
+///
+/// Dropping the writer finishes the stream, ignoring errors. After a failed transform or write of a block, or a
+/// failed write of the trailer, the writer is poisoned: further writes fail and dropping it writes nothing, so that
+/// no block or trailer is written twice.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{Read, Write};
+/// use sais::stream::{BwtReader, BwtWriter};
+///
+/// let mut writer = BwtWriter::new(Vec::new(), 4).unwrap();
+/// writer.write_all(b"mississippi").unwrap();
+/// let encoded = writer.finish().unwrap();
+///
+/// let mut decoded = Vec::new();
+/// BwtReader::new(&encoded[..]).unwrap().read_to_end(&mut decoded).unwrap();
+/// assert_eq!(decoded, b"mississippi");
+/// ```
+pub struct BwtWriter<W: Write> {
+    inner: Option<W>,
+    ctx: SaisContext,
+    block_size: usize,
+    buffer: Vec<u8>,
+    bwt: Vec<u8>,
+    tmp: Vec<i32>,
+    poisoned: bool,
+}
+
+impl<W: Write> BwtWriter<W> {
+    /// Creates a writer with blocks of `block_size` bytes, and writes the stream header.
+    ///
+    /// Returns error if `block_size` is zero or larger than `MAX_LENGTH`.
+    pub fn new(mut inner: W, block_size: usize) -> io::Result<Self> {
+        if block_size == 0 || block_size > MAX_LENGTH {
+            Err(sais_error(Error::IllegalArguments))?
+        }
+        let ctx = SaisContext::new().ok_or_else(|| sais_error(Error::InternalError))?;
+        write_header(&mut inner, MAGIC, block_size)?;
+        Ok(BwtWriter {
+            inner: Some(inner),
+            ctx,
+            block_size,
+            buffer: Vec::with_capacity(block_size),
+            bwt: Vec::new(),
+            tmp: Vec::new(),
+            poisoned: false,
+        })
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("writer is finished")
+    }
+
+    /// Writes pending block and the trailer, returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.try_finish()?;
+        Ok(self.inner.take().expect("writer is finished"))
+    }
+
+    fn try_finish(&mut self) -> io::Result<()> {
+        self.write_block()?;
+        self.poisoned = true;
+        let inner = self.inner.as_mut().expect("writer is finished");
+        write_u32(inner, 0)?;
+        inner.flush()
+    }
+
+    fn write_block(&mut self) -> io::Result<()> {
+        if self.poisoned {
+            Err(io::Error::other("writer is poisoned by a failed write"))?
+        }
+        let n = self.buffer.len();
+        if n == 0 {
+            return Ok(());
+        }
+        // neither a failed transform nor a partially written block is retried
+        self.poisoned = true;
+        self.bwt.resize(n, 0);
+        self.tmp.resize(n, 0);
+        let primary = self
+            .ctx
+            .bwt(&self.buffer, &mut self.bwt, &mut self.tmp, None)
+            .map_err(sais_error)?;
+        let inner = self.inner.as_mut().expect("writer is finished");
+        write_frame_start(inner, n, primary)?;
+        inner.write_all(&self.bwt)?;
+        self.poisoned = false;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for BwtWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.poisoned {
+            Err(io::Error::other("writer is poisoned by a failed write"))?
+        }
+        if self.buffer.len() == self.block_size && !buf.is_empty() {
+            self.write_block()?;
+        }
+        let n = buf.len().min(self.block_size - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    /// Writes pending input as a possibly short block, and flushes the underlying writer.
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_block()?;
+        self.inner.as_mut().expect("writer is finished").flush()
+    }
+}
+
+impl<W: Write> Drop for BwtWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() && !self.poisoned {
+            let _ = self.try_finish();
+        }
+    }
+}
+
+/// Bwt decoder reading framed blocks from the underlying reader.
+pub struct BwtReader<R: Read> {
+    inner: R,
+    ctx: UnbwtContext,
+    block_size: usize,
+    bwt: Vec<u8>,
+    tmp: Vec<i32>,
+    block: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl<R: Read> BwtReader<R> {
+    /// Creates a reader, and reads the stream header.
+    ///
+    /// Returns error of kind `InvalidData` if the header is malformed.
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let block_size = read_header(&mut inner, MAGIC)?;
+        let ctx = UnbwtContext::new().ok_or_else(|| sais_error(Error::InternalError))?;
+        Ok(BwtReader {
+            inner,
+            ctx,
+            block_size,
+            bwt: Vec::new(),
+            tmp: Vec::new(),
+            block: Vec::new(),
+            position: 0,
+            finished: false,
+        })
+    }
+
+    /// Block size declared in the stream header.
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Unwraps the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads and decodes next block, returns `false` at the trailer.
+    ///
+    /// Buffers grow as the block arrives rather than to its declared length, so that a corrupted length
+    /// cannot allocate more than the stream actually carries.
+    fn read_block(&mut self) -> io::Result<bool> {
+        let Some((n, primary)) = read_frame_start(&mut self.inner, self.block_size)? else {
+            return Ok(false);
+        };
+        self.bwt.clear();
+        (&mut self.inner)
+            .take(n as u64)
+            .read_to_end(&mut self.bwt)?;
+        if self.bwt.len() < n {
+            Err(io::Error::from(io::ErrorKind::UnexpectedEof))?
+        }
+
+        let mut freq = [0i32; 256];
+        for &c in self.bwt.iter() {
+            freq[c as usize] += 1;
+        }
+        self.block.resize(n, 0);
+        self.tmp.resize(n + 1, 0);
+        self.ctx
+            .unbwt(&self.bwt, &mut self.block, &mut self.tmp, Some(&freq), primary)
+            .map_err(sais_error)?;
+        self.position = 0;
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for BwtReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.block.len() && !self.finished {
+            self.finished = !self.read_block()?;
+        }
+        let n = buf.len().min(self.block.len() - self.position);
+        buf[..n].copy_from_slice(&self.block[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::alphabet::*;
+use crate::errors::Error;
+use crate::tests::common::*;
+
+fn check_sais_compact<S: num_traits::PrimInt + num_traits::AsPrimitive<usize> + std::fmt::Debug>(t: &[S], backend: Backend) {
+    let original = t.to_vec();
+    let mut sa = vec![0i32; t.len() + 10];
+    assert_eq!(sais_compact(t, &mut sa), Ok(backend));
+    assert_eq!(t, original);
+    check_suffix_array(t, &sa);
+}
+
+#[test]
+fn test_sais_compact() {
+    check_sais_compact::<u32>(&[], Backend::Sais32);
+    check_sais_compact::<u32>(&[u32::MAX, 0, u32::MAX, 0], Backend::Sais32);
+    check_sais_compact::<i64>(&[-5, i64::MAX, i64::MIN, -5, 0, i64::MIN], Backend::Sais32);
+
+    let t: Vec<u64> = random_text(1000..=2000, 0..=255)
+        .into_iter()
+        .map(|c: u64| c << 40)
+        .collect();
+    check_sais_compact(&t, Backend::Sais32);
+
+    // 1000 distinct symbols
+    let t: Vec<u64> = random_text::<u64>(3000..=4000, 0..=999)
+        .into_iter()
+        .map(|c| c.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        .collect();
+    let wide = if cfg!(feature = "sais16") { Backend::Sais16 } else { Backend::SaisInt };
+    check_sais_compact(&t, wide);
+
+    // more than 65536 distinct symbols
+    let mut t: Vec<i64> = (0..70000i64).map(|i| (i * 7919) % 70001 - 35000).collect();
+    t.extend_from_slice(&t.clone()[..100]);
+    check_sais_compact(&t, Backend::SaisInt);
+}
+
+#[test]
+fn test_alphabet() {
+    let t: [u32; 6] = [30, 10, 20, 30, 10, 30];
+    let alphabet = Alphabet::new(&t);
+    assert_eq!(alphabet.symbols(), [10, 20, 30]);
+    assert_eq!(alphabet.rank(20), Some(1));
+    assert_eq!(alphabet.rank(25), None);
+    assert_eq!(alphabet.backend(), Backend::Sais32);
+
+    let mut sa = vec![0; t.len()];
+    assert_eq!(alphabet.sais(&[10, 25], &mut sa), Err(Error::IllegalArguments));
+    assert_eq!(alphabet.sais(&t, &mut sa[..5]), Err(Error::IllegalArguments));
+}
@@ -1,11 +1,13 @@
 #![allow(unused)]
 
+use std::cmp::Ordering;
 use std::fmt::Debug;
 use std::ops::RangeInclusive;
 
 use crate::aux_index::{aux_length_exact, AUX_RATE_MIN};
 use num_traits::{one, zero};
 use num_traits::{AsPrimitive, NumAssignOps, PrimInt};
+use once_cell::sync::Lazy;
 use rand::distributions::uniform;
 use rand::prelude::*;
 
@@ -18,6 +20,24 @@ pub fn random_text<I: PrimInt + uniform::SampleUniform>(text_size: RangeInclusiv
     sample
 }
 
+/// Handwritten corner cases and random texts over binary, small and byte alphabets.
+pub static TEXTS: Lazy<Vec<Vec<u8>>> = Lazy::new(|| {
+    let mut samples = vec![
+        b"".to_vec(),
+        b"_".to_vec(),
+        b"aaaaaaaa".to_vec(),
+        b"banana".to_vec(),
+        b"abababc".to_vec(),
+        b"xabcyabcz".to_vec(),
+        b"mississippi".to_vec(),
+        b"abracadabra abracadabra".to_vec(),
+    ];
+    samples.push(random_text(50..=100, 0..=1));
+    samples.push(random_text(50..=100, 0..=4));
+    samples.push(random_text(100..=200, 0..=255));
+    samples
+});
+
 #[inline]
 pub fn allocate_suffix_arrays<I: PrimInt>(text_size: usize) -> Vec<Vec<I>> {
     vec![
@@ -34,7 +54,6 @@ pub fn allocate_suffix_arrays<I: PrimInt>(text_size: usize) -> Vec<Vec<I>> {
 pub fn allocate_aux_arrays<I: PrimInt>(text_size: usize) -> Vec<Vec<I>> {
     let max_rate = Ord::min(text_size.checked_next_power_of_two().unwrap(), AUX_RATE_MIN);
     let mut rates: Vec<usize> = (1..)
-        .into_iter()
         .map(|i| 1 << i)
         .take_while(|&r| r <= max_rate)
         .collect();
@@ -53,7 +72,7 @@ where
     TI: PrimInt + AsPrimitive<usize>,
     AI: PrimInt + AsPrimitive<usize>,
 {
-    if t.len() > 0 {
+    if !t.is_empty() {
         assert!(sa.len() >= t.len());
         for i in 0..t.len() - 1 {
             assert!(t[sa[i].as_()..] < t[sa[i + 1].as_()..]);
@@ -68,7 +87,7 @@ where
     AI: PrimInt + AsPrimitive<usize> + TryFrom<usize> + Debug,
     <AI as TryFrom<usize>>::Error: Debug,
 {
-    if t.len() > 0 {
+    if !t.is_empty() {
         assert!(sa.len() >= t.len());
         assert!(lcp.len() >= t.len());
         assert_eq!(lcp[0], zero());
@@ -93,3 +112,104 @@ where
     }
     assert_eq!(freq, rust_freq.as_slice());
 }
+
+#[inline]
+pub fn naive_common_prefix<T: Eq>(x: &[T], y: &[T]) -> usize {
+    Iterator::zip(x.iter(), y.iter())
+        .take_while(|(x, y)| x == y)
+        .count()
+}
+
+#[inline]
+#[cfg(feature = "sais32")]
+pub fn suffix_and_lcp_arrays_32(t: &[u8]) -> (Vec<i32>, Vec<i32>) {
+    use crate::sais32::{lcp, plcp, sais};
+
+    let mut sa = vec![0i32; t.len()];
+    let mut plcp_array = vec![0i32; t.len()];
+    let mut lcp_array = vec![0i32; t.len()];
+    sais(t, sa.as_mut_slice(), None).expect("sais failed");
+    plcp(t, sa.as_slice(), plcp_array.as_mut_slice()).expect("plcp failed");
+    lcp(plcp_array.as_slice(), sa.as_slice(), lcp_array.as_mut_slice()).expect("lcp failed");
+    (sa, lcp_array)
+}
+
+#[inline]
+#[cfg(feature = "sais64")]
+pub fn suffix_and_lcp_arrays_64(t: &[u8]) -> (Vec<i64>, Vec<i64>) {
+    use crate::sais64::{lcp, plcp, sais};
+
+    let mut sa = vec![0i64; t.len()];
+    let mut plcp_array = vec![0i64; t.len()];
+    let mut lcp_array = vec![0i64; t.len()];
+    sais(t, sa.as_mut_slice(), None).expect("sais failed");
+    plcp(t, sa.as_slice(), plcp_array.as_mut_slice()).expect("plcp failed");
+    lcp(plcp_array.as_slice(), sa.as_slice(), lcp_array.as_mut_slice()).expect("lcp failed");
+    (sa, lcp_array)
+}
+
+#[inline]
+pub fn check_cyclic_bwt<T: Ord + Copy + Debug>(t: &[T], u: &[T], primary: usize) {
+    let n = t.len();
+    let rotation = |p: usize| t[p..].iter().chain(t[..p].iter());
+    let mut rotations: Vec<usize> = (0..n).collect();
+    rotations.sort_by(|&x, &y| Iterator::cmp(rotation(x), rotation(y)));
+    let expected: Vec<T> = rotations.iter().map(|&p| t[(p + n - 1) % n]).collect();
+    assert_eq!(u, expected.as_slice());
+    if n > 0 {
+        assert!(Iterator::eq(rotation(rotations[primary]), t.iter()));
+    }
+}
+
+#[inline]
+pub fn naive_bbwt<T: Ord + Copy>(t: &[T]) -> Vec<T> {
+    // lyndon factors start at suffixes smaller than all suffixes before them
+    let n = t.len();
+    let mut sa: Vec<usize> = (0..n).collect();
+    sa.sort_by(|&x, &y| t[x..].cmp(&t[y..]));
+    let mut isa = vec![0; n];
+    for (rank, &p) in sa.iter().enumerate() {
+        isa[p] = rank;
+    }
+    let mut starts: Vec<usize> = Vec::new();
+    for i in 0..n {
+        if starts.last().is_none_or(|&s| isa[i] < isa[s]) {
+            starts.push(i);
+        }
+    }
+    starts.push(n);
+
+    // rotations r and s in omega order by comparing rs and sr
+    let mut rotations: Vec<[&[T]; 2]> = starts
+        .windows(2)
+        .flat_map(|w| (w[0]..w[1]).map(move |j| [&t[j..w[1]], &t[w[0]..j]]))
+        .collect();
+    rotations.sort_by(|r, s| cmp_segments(&[r[0], r[1], s[0], s[1]], &[s[0], s[1], r[0], r[1]]));
+    rotations
+        .iter()
+        .map(|r| *r[1].last().unwrap_or_else(|| r[0].last().unwrap()))
+        .collect()
+}
+
+/// Compares concatenations of segments, a slice at a time.
+fn cmp_segments<T: Ord>(x: &[&[T]], y: &[&[T]]) -> Ordering {
+    let mut x = x.iter().copied().filter(|s| !s.is_empty());
+    let mut y = y.iter().copied().filter(|s| !s.is_empty());
+    let (mut a, mut b): (&[T], &[T]) = (&[], &[]);
+    loop {
+        if a.is_empty() {
+            a = x.next().unwrap_or_default();
+        }
+        if b.is_empty() {
+            b = y.next().unwrap_or_default();
+        }
+        if a.is_empty() || b.is_empty() {
+            return a.len().cmp(&b.len());
+        }
+        let m = Ord::min(a.len(), b.len());
+        match a[..m].cmp(&b[..m]) {
+            Ordering::Equal => (a, b) = (&a[m..], &b[m..]),
+            ordering => return ordering,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::compress::*;
+use crate::errors::Error;
+use crate::tests::common::*;
+
+fn samples() -> Vec<Vec<u8>> {
+    vec![
+        b"".to_vec(),
+        b"a".to_vec(),
+        b"mississippi".to_vec(),
+        vec![0; 1000],
+        vec![0xff; 70000],
+        b"abracadabra".repeat(500),
+        random_text(1000..=2000, 0..=1),
+        random_text(1000..=2000, 0..=3),
+        random_text(10000..=20000, 0..=255),
+    ]
+}
+
+#[test]
+fn test_compress_decompress() {
+    for data in samples() {
+        for block_size in [data.len() / 3 + 1, 1000, DEFAULT_BLOCK_SIZE] {
+            let compressed = compress(&data, block_size).unwrap();
+            assert_eq!(decompress(&compressed).unwrap(), data);
+        }
+    }
+    for block_size in [1, 7] {
+        let compressed = compress(b"mississippi", block_size).unwrap();
+        assert_eq!(decompress(&compressed).unwrap(), b"mississippi");
+    }
+    let data = b"abracadabra".repeat(500);
+    assert!(compress(&data, DEFAULT_BLOCK_SIZE).unwrap().len() < data.len() / 10);
+}
+
+#[test]
+fn test_compress_illegal_arguments() {
+    assert_eq!(compress(b"abc", 0), Err(Error::IllegalArguments));
+    assert_eq!(compress(b"abc", MAX_BLOCK_SIZE + 1), Err(Error::IllegalArguments));
+}
+
+#[test]
+fn test_decompress_corrupted() {
+    let data = random_text(1000..=2000, 0..=3);
+    let compressed = compress(&data, 500).unwrap();
+    assert_eq!(decompress(b""), Err(Error::IllegalArguments));
+    assert_eq!(decompress(b"SBZ0\x00\x01\x00\x00\x00\x00\x00\x00"), Err(Error::IllegalArguments));
+    for n in 0..compressed.len() {
+        assert!(decompress(&compressed[..n]).is_err());
+    }
+    let mut extended = compressed.clone();
+    extended.push(0);
+    assert!(decompress(&extended).is_err());
+    for i in (0..compressed.len()).step_by(7) {
+        let mut corrupted = compressed.clone();
+        corrupted[i] ^= 0x10;
+        // flipped padding bits are not detected, but never produce wrong output
+        if let Ok(decompressed) = decompress(&corrupted) {
+            assert_eq!(decompressed, data);
+        }
+    }
+}
+
+#[test]
+fn test_crc32() {
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+}
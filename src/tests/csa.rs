@@ -0,0 +1,61 @@
+use crate::csa::*;
+use crate::index::SaIndex;
+use crate::tests::common::*;
+
+fn check_compressed_suffix_array<I: SaIndex>(t: &[u8], sa: &[I]) {
+    for rate in [1, 3, 16, 1000] {
+        let csa = CompressedSuffixArray::new(t, sa, rate).expect("CompressedSuffixArray::new failed");
+        assert_eq!(csa.len(), t.len());
+        for (i, &p) in sa.iter().enumerate() {
+            assert_eq!(csa.lookup(i), p.as_usize());
+            assert_eq!(csa.inverse(p.as_usize()), i);
+            assert_eq!(csa.symbol(p.as_usize()), t[p.as_usize()]);
+            match csa.psi(i) {
+                Some(next) => assert_eq!(sa[next].as_usize(), p.as_usize() + 1),
+                None => assert_eq!(p.as_usize(), t.len() - 1),
+            }
+        }
+        assert_eq!(csa.extract(0..t.len()), t);
+        for start in (0..t.len()).step_by(7) {
+            let end = Ord::min(start + 13, t.len());
+            assert_eq!(csa.extract(start..end), &t[start..end]);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_compressed_suffix_array_32() {
+    for t in TEXTS.iter() {
+        let (sa, _) = suffix_and_lcp_arrays_32(t);
+        check_compressed_suffix_array(t, &sa);
+    }
+}
+
+#[test]
+#[cfg(feature = "sais64")]
+fn test_compressed_suffix_array_64() {
+    for t in TEXTS.iter() {
+        let (sa, _) = suffix_and_lcp_arrays_64(t);
+        check_compressed_suffix_array(t, &sa);
+    }
+}
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_compressed_suffix_array_size() {
+    let t = b"abracadabra".repeat(1000);
+    let (sa, _) = suffix_and_lcp_arrays_32(&t);
+    let csa = CompressedSuffixArray::new(&t, &sa, 32).unwrap();
+    assert!(csa.size_in_bytes() < sa.len() * std::mem::size_of::<i32>());
+}
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_compressed_suffix_array_illegal_arguments() {
+    use crate::errors::Error;
+
+    assert_eq!(CompressedSuffixArray::new(b"ab", &[1, 0], 0).err(), Some(Error::IllegalArguments));
+    assert_eq!(CompressedSuffixArray::new(b"ab", &[1, 1], 2).err(), Some(Error::IllegalArguments));
+    assert_eq!(CompressedSuffixArray::new(b"ab", &[0i32], 2).err(), Some(Error::IllegalArguments));
+}
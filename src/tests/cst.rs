@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+
+use rand::prelude::*;
+
+use crate::csa::CompressedSuffixArray;
+use crate::cst::*;
+use crate::errors::Error;
+use crate::esa::EnhancedSuffixArray;
+use crate::tests::common::*;
+
+fn all_nodes(cst: &CompressedSuffixTree<i32>) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut stack = vec![cst.root()];
+    while let Some(v) = stack.pop() {
+        stack.extend(cst.children(&v));
+        nodes.push(v);
+    }
+    nodes
+}
+
+#[test]
+fn test_cst_nodes() {
+    // long enough to span several blocks of lcp minima
+    let long = random_text(500..=1000, 0..=2);
+    for t in TEXTS.iter().chain([&long]) {
+        let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+        let csa = CompressedSuffixArray::new(t, &sa, 4).unwrap();
+        let cst = CompressedSuffixTree::new(&csa, &lcp).expect("cst failed");
+        let nodes = all_nodes(&cst);
+
+        // internal nodes are lcp-intervals, plus root and leaves
+        let n = t.len();
+        let esa = EnhancedSuffixArray::new(&sa, &lcp).unwrap();
+        let mut expected: HashSet<_> = esa
+            .intervals()
+            .filter(|x| x.range.len() > 1)
+            .map(|x| (x.range, x.lcp))
+            .collect();
+        expected.insert((0..n, 0));
+        expected.extend((0..n).map(|r| (r..r + 1, n - sa[r] as usize)));
+        let actual: HashSet<_> = nodes.iter().map(|v| (v.range.clone(), v.depth)).collect();
+        assert_eq!(actual.len(), nodes.len());
+        assert_eq!(actual, expected);
+
+        for v in nodes.iter() {
+            let label = cst.label(v);
+            assert!(cst
+                .suffixes(v)
+                .eq(sa[v.range.clone()].iter().map(|&p| p as usize)));
+            for p in cst.suffixes(v) {
+                assert_eq!(&t[p..p + v.depth], label);
+            }
+            assert_eq!(cst.is_leaf(v), v.range.len() == 1 && !cst.is_root(v));
+
+            let children = cst.children(v);
+            for (k, u) in children.iter().enumerate() {
+                assert_eq!(cst.parent(u).as_ref(), Some(v));
+                assert!(u.depth >= v.depth);
+                if k > 0 {
+                    assert_eq!(children[k - 1].range.end, u.range.start);
+                }
+                match cst.label(u).get(v.depth) {
+                    Some(&c) => assert_eq!(cst.child(v, c).as_ref(), Some(u)),
+                    None => assert!(cst.is_leaf(u) && u.depth == v.depth),
+                }
+            }
+            if !children.is_empty() {
+                assert_eq!(children[0].range.start..children[children.len() - 1].range.end, v.range);
+            }
+            assert_eq!(cst.parent(v).is_none(), cst.is_root(v));
+
+            match cst.suffix_link(v) {
+                Some(w) => {
+                    assert_eq!(cst.label(&w), &label[1..]);
+                    assert!(actual.contains(&(w.range, w.depth)));
+                }
+                None => assert!(cst.is_root(v)),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_cst_child_missing() {
+    let t = b"mississippi";
+    let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+    let csa = CompressedSuffixArray::new(t, &sa, 4).unwrap();
+    let cst = CompressedSuffixTree::new(&csa, &lcp).unwrap();
+    assert_eq!(cst.child(&cst.root(), b'x'), None);
+    let ssi = cst
+        .child(&cst.child(&cst.root(), b's').unwrap(), b's')
+        .unwrap();
+    assert_eq!(cst.label(&ssi), b"ssi");
+    assert_eq!(cst.child(&ssi, b'i'), None);
+    assert_eq!(cst.children(&cst.leaf(0)), []);
+}
+
+#[test]
+fn test_cst_lca() {
+    let mut rng = thread_rng();
+    for t in TEXTS.iter().filter(|t| !t.is_empty()) {
+        let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+        let csa = CompressedSuffixArray::new(t, &sa, 4).unwrap();
+        let cst = CompressedSuffixTree::new(&csa, &lcp).unwrap();
+        let nodes = all_nodes(&cst);
+        for _ in 0..200 {
+            let (i, j) = (rng.gen_range(0..t.len()), rng.gen_range(0..t.len()));
+            let lca = cst.lca(&cst.leaf(i), &cst.leaf(j));
+            assert_eq!(lca.depth, naive_common_prefix(&t[i..], &t[j..]));
+            assert!(lca.range.contains(&(cst.leaf(i).range.start)));
+            assert!(lca.range.contains(&(cst.leaf(j).range.start)));
+
+            let u = nodes.choose(&mut rng).unwrap();
+            let v = nodes.choose(&mut rng).unwrap();
+            let lca = cst.lca(u, v);
+            assert!(nodes.contains(&lca));
+            let ancestors = |x: &Node| std::iter::successors(Some(x.clone()), |y| cst.parent(y)).collect::<Vec<_>>();
+            let common = ancestors(u).into_iter().find(|x| ancestors(v).contains(x));
+            assert_eq!(Some(lca), common);
+        }
+    }
+}
+
+#[test]
+fn test_cst_illegal_arguments() {
+    let csa = CompressedSuffixArray::new(b"abc", &[0, 1, 2], 2).unwrap();
+    assert_eq!(CompressedSuffixTree::new(&csa, &[0, 0]).err(), Some(Error::IllegalArguments));
+    assert_eq!(CompressedSuffixTree::new(&csa, &[0, 0, 0, 0]).err(), Some(Error::IllegalArguments));
+    assert_eq!(CompressedSuffixTree::new(&csa, &[0, -1, 0]).err(), Some(Error::IllegalArguments));
+}
@@ -0,0 +1,70 @@
+use crate::ebwt::*;
+use crate::errors::Error;
+use crate::tests::common::*;
+
+fn samples() -> Vec<Vec<Vec<u8>>> {
+    vec![
+        vec![],
+        vec![b"".to_vec()],
+        vec![b"".to_vec(), b"".to_vec(), b"".to_vec()],
+        vec![b"banana".to_vec()],
+        vec![b"ACGT".to_vec(), b"".to_vec(), b"GATTACA".to_vec()],
+        vec![
+            b"aaa".to_vec(),
+            b"aaa".to_vec(),
+            b"aa".to_vec(),
+            b"aaaa".to_vec(),
+        ],
+        vec![
+            b"\x00\xff".to_vec(),
+            b"\x00".to_vec(),
+            b"\xff\x00\x00".to_vec(),
+        ],
+        (0..10).map(|_| random_text(0..=20, 0..=1)).collect(),
+        (0..10).map(|_| random_text(0..=30, 0..=3)).collect(),
+        (0..5).map(|_| random_text(0..=50, 0..=255)).collect(),
+    ]
+}
+
+// terminator of i-th string is (0, i), symbol c is (1, c)
+fn naive_ebwt(strings: &[Vec<u8>]) -> CollectionBwt {
+    let terminated: Vec<Vec<(u8, usize)>> = strings
+        .iter()
+        .enumerate()
+        .map(|(i, s)| s.iter().map(|&c| (1, c as usize)).chain([(0, i)]).collect())
+        .collect();
+    let mut suffixes: Vec<(usize, usize)> = terminated
+        .iter()
+        .enumerate()
+        .flat_map(|(i, s)| (0..s.len()).map(move |p| (i, p)))
+        .collect();
+    suffixes.sort_by(|&(i, p), &(j, q)| terminated[i][p..].cmp(&terminated[j][q..]));
+    let mut bwt = vec![0; suffixes.len()];
+    let mut terminators = vec![0; strings.len()];
+    for (row, &(i, p)) in suffixes.iter().enumerate() {
+        match p {
+            0 => terminators[i] = row as i32,
+            _ => bwt[row] = terminated[i][p - 1].1 as u8,
+        }
+    }
+    CollectionBwt { bwt, terminators }
+}
+
+#[test]
+fn test_ebwt_unebwt() {
+    for strings in samples() {
+        let collection = ebwt(&strings).unwrap();
+        assert_eq!(collection, naive_ebwt(&strings));
+        assert_eq!(unebwt(&collection.bwt, &collection.terminators).unwrap(), strings);
+    }
+}
+
+#[test]
+fn test_unebwt_illegal_arguments() {
+    let collection = ebwt(&[&b"abc"[..], b"ab"]).unwrap();
+    assert_eq!(unebwt(&collection.bwt, &[7]), Err(Error::IllegalArguments));
+    assert_eq!(unebwt(&collection.bwt, &[-1]), Err(Error::IllegalArguments));
+    let t = collection.terminators[0];
+    assert_eq!(unebwt(&collection.bwt, &[t, t]), Err(Error::IllegalArguments));
+    assert_eq!(unebwt(b"ab", &[0, 1, 0]), Err(Error::IllegalArguments));
+}
@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+
+use crate::errors::Error;
+use crate::esa::*;
+use crate::tests::common::*;
+
+#[test]
+fn test_lcp_intervals() {
+    for t in TEXTS.iter() {
+        let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+        let esa = EnhancedSuffixArray::new(sa.as_slice(), lcp.as_slice()).expect("esa failed");
+        let intervals: Vec<LcpInterval> = esa.intervals().collect();
+
+        // same set of intervals as brute force
+        let n = t.len();
+        let mut expected = HashSet::new();
+        for i in 0..n {
+            for j in i + 2..=n {
+                let l = lcp[i + 1..j].iter().copied().min().unwrap();
+                if (i == 0 || lcp[i] < l) && (j == n || lcp[j] < l) {
+                    expected.insert((i..j, l as usize));
+                }
+            }
+        }
+        if n > 0 && !expected.contains(&(0..n, lcp[1..].iter().copied().min().unwrap_or(0) as usize)) {
+            expected.insert((0..n, 0));
+        }
+        let actual: HashSet<_> = intervals.iter().map(|x| (x.range.clone(), x.lcp)).collect();
+        assert_eq!(actual.len(), intervals.len());
+        assert_eq!(actual, expected);
+
+        for (order, interval) in intervals.iter().enumerate() {
+            // suffixes share exactly lcp symbols
+            let suffixes = esa.suffixes(interval);
+            let first = &t[suffixes[0] as usize..];
+            let common = suffixes
+                .iter()
+                .map(|&p| naive_common_prefix(first, &t[p as usize..]))
+                .min()
+                .unwrap();
+            if interval.range.len() > 1 {
+                assert_eq!(common, interval.lcp);
+            }
+
+            // children partition the interval, and non-leaf children are visited before their parent
+            let children: Vec<_> = interval.children().collect();
+            assert_eq!(children.len(), interval.child_count());
+            assert_eq!(children.first().unwrap().start, interval.range.start);
+            assert_eq!(children.last().unwrap().end, interval.range.end);
+            for pair in children.windows(2) {
+                assert_eq!(pair[0].end, pair[1].start);
+            }
+            for child in children.into_iter().filter(|child| child.len() > 1) {
+                let position = intervals.iter().position(|x| x.range == child).unwrap();
+                assert!(position < order);
+                assert!(intervals[position].lcp > interval.lcp);
+            }
+        }
+
+        if n > 0 {
+            assert!(intervals.last().unwrap().is_root(n));
+        } else {
+            assert!(intervals.is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_esa_illegal_arguments() {
+    assert_eq!(EnhancedSuffixArray::new(&[0i32, 1], &[0i32]).err(), Some(Error::IllegalArguments));
+    assert_eq!(EnhancedSuffixArray::new(&[0i32, 1], &[0i32, -1]).err(), Some(Error::IllegalArguments));
+}
@@ -0,0 +1,183 @@
+use crate::aux_index::*;
+use crate::extract::*;
+use crate::tests::common::*;
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_extract_32() {
+    use crate::sais32::*;
+
+    for t in TEXTS.iter() {
+        for rate in [2, 4, 16, 128] {
+            let mut u = vec![0; t.len()];
+            let mut a = vec![0; t.len()];
+            let mut freq = vec![0; FREQ_TABLE_SIZE];
+            let mut aux = vec![0; aux_length_exact(t.len(), rate).unwrap()];
+            bwt_aux(t, &mut u, &mut a, Some(&mut freq), &mut aux).expect("bwt failed");
+
+            let index = LfIndex::new(&u, &freq, &aux).expect("LfIndex::new failed");
+            assert_eq!(index.len(), t.len());
+            for start in 0..=t.len() {
+                for end in start..=t.len() {
+                    assert_eq!(index.extract(start..end), &t[start..end]);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "sais16")]
+fn test_extract_16() {
+    use crate::sais16::*;
+
+    for t in TEXTS.iter() {
+        let t: Vec<u16> = t.iter().map(|&c| c as u16 * 251).collect();
+        let mut u = vec![0; t.len()];
+        let mut a = vec![0; t.len()];
+        let mut freq = vec![0; FREQ_TABLE_SIZE];
+        let mut aux = vec![0; aux_length_exact(t.len(), 8).unwrap()];
+        bwt_aux(&t, &mut u, &mut a, Some(&mut freq), &mut aux).expect("bwt failed");
+
+        let index = LfIndex::new(&u, &freq, &aux).expect("LfIndex::new failed");
+        let mut out = vec![0; t.len() / 2];
+        index.extract_into(t.len() / 4..t.len() / 4 + out.len(), &mut out);
+        assert_eq!(out, &t[t.len() / 4..t.len() / 4 + out.len()]);
+    }
+}
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_extract_illegal_arguments() {
+    use crate::errors::Error;
+    use crate::sais32::*;
+
+    let t = b"abracadabra";
+    let mut u = vec![0; t.len()];
+    let mut a = vec![0; t.len()];
+    let mut freq = vec![0; FREQ_TABLE_SIZE];
+    let mut aux = vec![0; aux_length_exact(t.len(), 4).unwrap()];
+    bwt_aux(t, &mut u, &mut a, Some(&mut freq), &mut aux).expect("bwt failed");
+
+    assert!(LfIndex::new(&u, &freq, &aux).is_ok());
+    assert_eq!(LfIndex::new(&u, &freq[..b'r' as usize], &aux).err(), Some(Error::IllegalArguments));
+    assert_eq!(LfIndex::new(&u, &freq, &[aux.clone(), vec![1]].concat()).err(), Some(Error::IllegalArguments));
+    assert_eq!(LfIndex::new(&u, &freq, &[0, aux[1], aux[2]]).err(), Some(Error::IllegalArguments));
+    freq[b'a' as usize] -= 1;
+    freq[b'b' as usize] += 1;
+    assert_eq!(LfIndex::new(&u, &freq, &aux).err(), Some(Error::IllegalArguments));
+}
+
+#[test]
+#[should_panic]
+#[cfg(feature = "sais32")]
+fn test_extract_out_of_bounds() {
+    use crate::sais32::*;
+
+    let t = b"abracadabra";
+    let mut u = vec![0; t.len()];
+    let mut a = vec![0; t.len()];
+    let mut aux = vec![0; aux_length_exact(t.len(), 4).unwrap()];
+    let mut freq = vec![0; FREQ_TABLE_SIZE];
+    bwt_aux(t, &mut u, &mut a, Some(&mut freq), &mut aux).expect("bwt failed");
+    LfIndex::new(&u, &freq, &aux)
+        .expect("LfIndex::new failed")
+        .extract(5..12);
+}
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_unbwt_aux_threads_32() {
+    use crate::sais32::*;
+
+    let mut texts = TEXTS.clone();
+    texts.push(random_text(5000..=10000, 0..=3));
+    for t in texts.iter() {
+        for rate in [2, 16, 1024] {
+            let mut u = vec![0; t.len()];
+            let mut a = vec![0; t.len()];
+            let mut freq = vec![0; FREQ_TABLE_SIZE];
+            let mut aux = vec![0; aux_length_exact(t.len(), rate).unwrap()];
+            bwt_aux(t, &mut u, &mut a, Some(&mut freq), &mut aux).expect("bwt failed");
+
+            for threads in [0, 1, 3, 8] {
+                let mut s = vec![0; t.len()];
+                unbwt_aux_threads(&u, &mut s, Some(&freq), &aux, threads).expect("unbwt failed");
+                assert_eq!(&s, t);
+                let mut s = vec![0; t.len()];
+                unbwt_aux_threads(&u, &mut s, None, &aux, threads).expect("unbwt failed");
+                assert_eq!(&s, t);
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "sais16")]
+fn test_unbwt_aux_threads_16() {
+    use crate::sais16::*;
+
+    for t in TEXTS.iter() {
+        let t: Vec<u16> = t.iter().map(|&c| c as u16 * 251).collect();
+        let mut u = vec![0; t.len()];
+        let mut a = vec![0; t.len()];
+        let mut aux = vec![0; aux_length_exact(t.len(), 4).unwrap()];
+        bwt_aux(&t, &mut u, &mut a, None, &mut aux).expect("bwt failed");
+
+        let mut s = vec![0; t.len()];
+        unbwt_aux_threads(&u, &mut s, None, &aux, 4).expect("unbwt failed");
+        assert_eq!(s, t);
+    }
+}
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_unbwt_aux_threads_illegal_arguments() {
+    use crate::errors::Error;
+    use crate::sais32::*;
+
+    let t = b"abracadabra";
+    let mut u = vec![0; t.len()];
+    let mut a = vec![0; t.len()];
+    let mut aux = vec![0; aux_length_exact(t.len(), 2).unwrap()];
+    bwt_aux(t, &mut u, &mut a, None, &mut aux).expect("bwt failed");
+
+    let mut s = vec![0; t.len() + 1];
+    assert_eq!(unbwt_aux_threads(&u, &mut s, None, &aux, 2), Err(Error::IllegalArguments));
+    let mut s = vec![0; t.len()];
+    assert_eq!(unbwt_aux_threads(&u, &mut s, None, &aux[1..], 2), Err(Error::IllegalArguments));
+    assert_eq!(unbwt_aux_threads(&u, &mut s, None, &aux, -1), Err(Error::IllegalArguments));
+}
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_owned_bwt_32() {
+    use crate::errors::Error;
+    use crate::sais32::*;
+
+    for t in TEXTS.iter() {
+        let bwt = Bwt::new(t, 4, bwt_aux).expect("Bwt::new failed");
+        assert_eq!(bwt.primary, bwt.aux[0]);
+        assert_eq!(bwt.freq.len(), FREQ_TABLE_SIZE);
+        assert_eq!(bwt.lf_index().expect("lf_index failed").extract(0..t.len()), *t);
+
+        let mut s = vec![0; t.len()];
+        let mut a = vec![0; t.len() + 1];
+        unbwt_aux(&bwt.bwt, &mut s, &mut a, Some(&bwt.freq), &bwt.aux).expect("unbwt failed");
+        assert_eq!(s, *t);
+    }
+    assert_eq!(Bwt::new(b"abracadabra", 3, bwt_aux), Err(Error::IllegalArguments));
+}
+
+#[test]
+#[cfg(feature = "sais16")]
+fn test_owned_bwt_16() {
+    use crate::sais16::*;
+
+    for t in TEXTS.iter() {
+        let t: Vec<u16> = t.iter().map(|&c| c as u16 * 251).collect();
+        let bwt = Bwt::new(&t, 8, bwt_aux).expect("Bwt::new failed");
+        assert_eq!(bwt.freq.len(), FREQ_TABLE_SIZE);
+        assert_eq!(bwt.lf_index().expect("lf_index failed").extract(0..t.len()), t);
+    }
+}
@@ -0,0 +1,172 @@
+use std::io::ErrorKind;
+
+use memmap2::MmapMut;
+
+use crate::aux_index::aux_length_exact;
+use crate::esa::EnhancedSuffixArray;
+use crate::extract::LfIndex;
+use crate::file::*;
+use crate::sais32::{bwt_aux, FREQ_TABLE_SIZE};
+use crate::tests::common::*;
+
+fn load(bytes: &[u8], verify_checksum: bool) -> std::io::Result<MappedIndex> {
+    let mut mmap = MmapMut::map_anon(bytes.len()).unwrap();
+    mmap.copy_from_slice(bytes);
+    MappedIndex::from_mmap(mmap.make_read_only().unwrap(), verify_checksum)
+}
+
+#[test]
+fn test_index_file_round_trip_32() {
+    for t in [
+        b"".to_vec(),
+        b"mississippi".to_vec(),
+        random_text(1000..=2000, 0..=255),
+    ] {
+        let (sa, lcp) = suffix_and_lcp_arrays_32(&t);
+        let mut u = vec![0; t.len()];
+        let mut a = vec![0; t.len()];
+        let mut freq = vec![0; FREQ_TABLE_SIZE];
+        let mut aux = vec![0; aux_length_exact(t.len(), 16).unwrap()];
+        bwt_aux(&t, &mut u, &mut a, Some(&mut freq), &mut aux).unwrap();
+
+        let index = Index {
+            primary: aux.first().map(|&i| i as usize),
+            text: Some(&t[..]),
+            sa: Some(&sa[..]),
+            lcp: Some(&lcp[..]),
+            bwt: Some(&u[..]),
+            aux: Some(&aux[..]),
+            freq: Some(&freq[..]),
+            ..Index::new(t.len())
+        };
+        let mut bytes = Vec::new();
+        index.write_to(&mut bytes).unwrap();
+        assert_eq!(&bytes[..8], MAGIC);
+        assert_eq!(bytes.len() % 8, 0);
+
+        let mapped = load(&bytes, true).unwrap();
+        assert_eq!(mapped.backend(), Backend::Sais32);
+        assert_eq!(mapped.len(), t.len());
+        assert_eq!(mapped.primary(), index.primary);
+        let loaded = mapped.index::<u8, i32>().unwrap();
+        assert_eq!(loaded.text, index.text);
+        assert_eq!(loaded.sa, index.sa);
+        assert_eq!(loaded.lcp, index.lcp);
+        assert_eq!(loaded.bwt, index.bwt);
+        assert_eq!(loaded.aux, index.aux);
+        assert_eq!(loaded.freq, index.freq);
+
+        // borrowed slices plug into search structures
+        let esa = EnhancedSuffixArray::new(loaded.sa.unwrap(), loaded.lcp.unwrap()).unwrap();
+        assert_eq!(esa.len(), t.len());
+        let lf = LfIndex::new(loaded.bwt.unwrap(), loaded.freq.unwrap(), loaded.aux.unwrap()).unwrap();
+        assert_eq!(lf.extract(0..t.len()), t);
+    }
+}
+
+#[test]
+#[cfg(feature = "sais64")]
+fn test_index_file_round_trip_64() {
+    let t = random_text(1000..=2000, 0..=3);
+    let (sa, lcp) = suffix_and_lcp_arrays_64(&t);
+    let path = std::env::temp_dir().join(format!("sais-test-{}.idx", std::process::id()));
+    Index {
+        sa: Some(&sa[..]),
+        lcp: Some(&lcp[..]),
+        ..Index::<u8, i64>::new(t.len())
+    }
+    .save(&path)
+    .unwrap();
+
+    let mapped = unsafe { MappedIndex::open(&path, true) }.unwrap();
+    assert_eq!(mapped.backend(), Backend::Sais64);
+    assert_eq!(mapped.primary(), None);
+    assert_eq!(mapped.index::<u8, i32>().err().map(|e| e.kind()), Some(ErrorKind::InvalidInput));
+    let loaded = mapped.index::<u8, i64>().unwrap();
+    assert_eq!(loaded.sa, Some(&sa[..]));
+    assert_eq!(loaded.lcp, Some(&lcp[..]));
+    assert_eq!(loaded.text, None);
+    drop(mapped);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_index_file_corrupted() {
+    let t = b"abracadabra";
+    let (sa, _) = suffix_and_lcp_arrays_32(t);
+    let mut bytes = Vec::new();
+    Index {
+        text: Some(&t[..]),
+        sa: Some(&sa[..]),
+        ..Index::new(t.len())
+    }
+    .write_to(&mut bytes)
+    .unwrap();
+
+    for p in 0..bytes.len() {
+        let mut corrupted = bytes.clone();
+        corrupted[p] ^= 0x10;
+        assert!(load(&corrupted, true).is_err(), "flipped byte {}", p);
+    }
+
+    // payload corruption is detected only by checksum
+    let mut corrupted = bytes.clone();
+    *corrupted.last_mut().unwrap() ^= 1;
+    assert!(load(&corrupted, false).is_ok());
+
+    let mut newer = bytes.clone();
+    newer[8] = 2;
+    assert_eq!(load(&newer, false).err().map(|e| e.kind()), Some(ErrorKind::Unsupported));
+
+    assert_eq!(
+        load(&bytes[..bytes.len() - 8], false)
+            .err()
+            .map(|e| e.kind()),
+        Some(ErrorKind::InvalidData)
+    );
+    assert_eq!(load(&bytes[..32], false).err().map(|e| e.kind()), Some(ErrorKind::InvalidData));
+    assert_eq!(load(b"", false).err().map(|e| e.kind()), Some(ErrorKind::InvalidData));
+}
+
+#[test]
+fn test_index_file_invalid_arrays() {
+    let sa = [0i32, 1, 2];
+    let short = [0i32, 1];
+    let freq = [0i32; 16];
+    let kind = |index: Index<u8, i32>| index.write_to(Vec::new()).err().map(|e| e.kind());
+    assert_eq!(
+        kind(Index {
+            sa: Some(&short),
+            ..Index::new(3)
+        }),
+        Some(ErrorKind::InvalidInput)
+    );
+    assert_eq!(
+        kind(Index {
+            freq: Some(&freq),
+            ..Index::new(3)
+        }),
+        Some(ErrorKind::InvalidInput)
+    );
+    assert_eq!(
+        kind(Index {
+            aux: Some(&sa),
+            ..Index::new(3)
+        }),
+        Some(ErrorKind::InvalidInput)
+    );
+    assert_eq!(
+        kind(Index {
+            primary: Some(4),
+            ..Index::new(3)
+        }),
+        Some(ErrorKind::InvalidInput)
+    );
+    assert_eq!(
+        Index::<u16, i64>::new(0)
+            .write_to(Vec::new())
+            .err()
+            .map(|e| e.kind()),
+        Some(ErrorKind::InvalidInput)
+    );
+}
@@ -0,0 +1,47 @@
+use rand::prelude::*;
+
+use crate::errors::Error;
+use crate::lce::*;
+use crate::tests::common::*;
+
+#[test]
+fn test_lce_exhaustive() {
+    for t in TEXTS.iter() {
+        let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+        let index = LceIndex::new(sa.as_slice(), lcp.as_slice()).expect("lce index failed");
+        assert_eq!(index.len(), t.len());
+        for i in 0..t.len() {
+            for j in 0..t.len() {
+                assert_eq!(index.lce(i, j), naive_common_prefix(&t[i..], &t[j..]));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_lce_random() {
+    let mut rng = thread_rng();
+    let long_texts = [
+        random_text(1000..=2000, 0..=2),
+        random_text(1000..=2000, 0..=255),
+    ];
+    for t in TEXTS
+        .iter()
+        .chain(long_texts.iter())
+        .filter(|t| !t.is_empty())
+    {
+        let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+        let index = LceIndex::new(sa.as_slice(), lcp.as_slice()).expect("lce index failed");
+        for _ in 0..1000 {
+            let (i, j) = (rng.gen_range(0..t.len()), rng.gen_range(0..t.len()));
+            assert_eq!(index.lce(i, j), naive_common_prefix(&t[i..], &t[j..]));
+        }
+    }
+}
+
+#[test]
+fn test_lce_illegal_arguments() {
+    assert_eq!(LceIndex::new(&[0i32, 1], &[0i32]).err(), Some(Error::IllegalArguments));
+    assert_eq!(LceIndex::new(&[0i32, 0], &[0i32, 0]).err(), Some(Error::IllegalArguments));
+    assert_eq!(LceIndex::new(&[0i32, 2], &[0i32, 0]).err(), Some(Error::IllegalArguments));
+}
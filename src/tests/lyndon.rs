@@ -0,0 +1,116 @@
+use crate::tests::common::*;
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_lyndon_array_32() {
+    use crate::sais32::*;
+
+    for t in TEXTS.iter() {
+        let mut sa = vec![0i32; t.len() + 16];
+        let mut lyndon = vec![0i32; t.len()];
+        lyndon_array(t, sa.as_mut_slice(), lyndon.as_mut_slice()).expect("lyndon_array failed");
+        check_lyndon_array(t, &lyndon);
+        check_lyndon_factorization(t, &lyndon);
+
+        let mut int_text: Vec<i32> = t.iter().map(|&c| c as i32).collect();
+        lyndon_array_int(int_text.as_mut_slice(), sa.as_mut_slice(), lyndon.as_mut_slice(), 256).expect("lyndon_array_int failed");
+        check_lyndon_array(t, &lyndon);
+    }
+}
+
+#[test]
+#[cfg(all(feature = "sais32", feature = "parallel"))]
+fn test_lyndon_array_32_parallel() {
+    use crate::sais32::parallel::*;
+
+    for t in TEXTS.iter() {
+        let mut sa = vec![0i32; t.len()];
+        let mut lyndon = vec![0i32; t.len()];
+        lyndon_array(t, sa.as_mut_slice(), lyndon.as_mut_slice(), 0).expect("lyndon_array failed");
+        check_lyndon_array(t, &lyndon);
+
+        let mut int_text: Vec<i32> = t.iter().map(|&c| c as i32).collect();
+        lyndon_array_int(int_text.as_mut_slice(), sa.as_mut_slice(), lyndon.as_mut_slice(), 256, 0).expect("lyndon_array_int failed");
+        check_lyndon_array(t, &lyndon);
+    }
+}
+
+#[test]
+#[cfg(feature = "sais16")]
+fn test_lyndon_array_16() {
+    use crate::sais16::*;
+
+    let mut texts: Vec<Vec<u16>> = TEXTS
+        .iter()
+        .map(|t| t.iter().map(|&c| c as u16 * 251).collect())
+        .collect();
+    texts.push(random_text(100..=200, 0..=65535));
+    for t in texts.iter() {
+        let mut sa = vec![0i32; t.len()];
+        let mut lyndon = vec![0i32; t.len()];
+        lyndon_array(t, sa.as_mut_slice(), lyndon.as_mut_slice()).expect("lyndon_array failed");
+        check_lyndon_array(t, &lyndon);
+        #[cfg(feature = "parallel")]
+        {
+            parallel::lyndon_array(t, sa.as_mut_slice(), lyndon.as_mut_slice(), 0).expect("lyndon_array failed");
+            check_lyndon_array(t, &lyndon);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "sais64")]
+fn test_lyndon_array_64() {
+    use crate::sais64::*;
+
+    for t in TEXTS.iter() {
+        let mut sa = vec![0i64; t.len()];
+        let mut lyndon = vec![0i64; t.len()];
+        lyndon_array(t, sa.as_mut_slice(), lyndon.as_mut_slice()).expect("lyndon_array failed");
+        check_lyndon_array(t, &lyndon);
+        #[cfg(feature = "parallel")]
+        {
+            parallel::lyndon_array(t, sa.as_mut_slice(), lyndon.as_mut_slice(), 0).expect("lyndon_array failed");
+            check_lyndon_array(t, &lyndon);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_lyndon_array_invalid() {
+    use crate::errors::Error;
+    use crate::lyndon::lyndon_array;
+
+    let mut lyndon = vec![0i32; 3];
+    assert_eq!(lyndon_array(&[0, 1], &mut lyndon), Err(Error::IllegalArguments));
+    assert_eq!(lyndon_array(&[0, 3, 1], &mut lyndon), Err(Error::IllegalArguments));
+    assert_eq!(lyndon_array(&[0, -1, 1], &mut lyndon), Err(Error::IllegalArguments));
+    assert_eq!(lyndon_array(&[0, 2, 0], &mut lyndon), Err(Error::IllegalArguments));
+    assert_eq!(lyndon_array(&[2, 1, 0], &mut lyndon), Ok(()));
+}
+
+fn check_lyndon_array<T: Ord, I: Copy + TryInto<usize>>(t: &[T], lyndon: &[I]) {
+    assert_eq!(t.len(), lyndon.len());
+    for i in 0..t.len() {
+        let expected = (1..=t.len() - i)
+            .filter(|&l| is_lyndon_word(&t[i..i + l]))
+            .max()
+            .unwrap();
+        assert_eq!(lyndon[i].try_into().ok(), Some(expected));
+    }
+}
+
+#[cfg(feature = "sais32")]
+fn check_lyndon_factorization<T: Ord, I: crate::index::SaIndex>(t: &[T], lyndon: &[I]) {
+    let factors: Vec<&[T]> = crate::lyndon::lyndon_factorization(lyndon)
+        .map(|range| &t[range])
+        .collect();
+    assert_eq!(factors.iter().map(|f| f.len()).sum::<usize>(), t.len());
+    assert!(factors.iter().all(|f| is_lyndon_word(f)));
+    assert!(factors.windows(2).all(|pair| pair[0] >= pair[1]));
+}
+
+fn is_lyndon_word<T: Ord>(w: &[T]) -> bool {
+    !w.is_empty() && (1..w.len()).all(|k| w < &w[k..])
+}
@@ -0,0 +1,81 @@
+use crate::lz77::*;
+use crate::tests::common::*;
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_longest_previous_factor_32() {
+    for t in TEXTS.iter() {
+        let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+        let result = longest_previous_factor(sa.as_slice(), lcp.as_slice()).unwrap();
+        check_longest_previous_factor(t, &result.lpf, &result.prev_occ);
+    }
+}
+
+#[test]
+#[cfg(feature = "sais64")]
+fn test_longest_previous_factor_64() {
+    for t in TEXTS.iter() {
+        let (sa, lcp) = suffix_and_lcp_arrays_64(t);
+        let result = longest_previous_factor(sa.as_slice(), lcp.as_slice()).unwrap();
+        check_longest_previous_factor(t, &result.lpf, &result.prev_occ);
+    }
+}
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_lz77_factorization() {
+    for t in TEXTS.iter() {
+        let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+        let phrases = lz77(t.as_slice(), sa.as_slice(), lcp.as_slice()).unwrap();
+        assert_eq!(&lz77_decode(&phrases), t);
+
+        // greedy parsing, each phrase is the longest previous factor or a new symbol
+        let mut i = 0;
+        for phrase in phrases.iter() {
+            let longest = naive_longest_previous_factor(t, i).0;
+            match *phrase {
+                Phrase::Literal(c) => {
+                    assert_eq!(longest, 0);
+                    assert_eq!(c, t[i]);
+                }
+                Phrase::Copy { source, length } => {
+                    assert_eq!(length, longest);
+                    assert!(source < i);
+                }
+            }
+            i += phrase.len();
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_lz77_illegal_arguments() {
+    use crate::errors::Error;
+
+    assert_eq!(longest_previous_factor(&[0i32, 1], &[0i32]).err(), Some(Error::IllegalArguments));
+    assert_eq!(longest_previous_factor(&[0i32, 2], &[0i32, 0]).err(), Some(Error::IllegalArguments));
+    assert_eq!(lz77(b"abc", &[0i32, 1], &[0i32, 0]).err(), Some(Error::IllegalArguments));
+}
+
+fn check_longest_previous_factor<I: Copy + TryInto<usize>>(t: &[u8], lpf: &[I], prev_occ: &[I]) {
+    let as_usize = |x: I| x.try_into().ok().unwrap();
+    for i in 0..t.len() {
+        let (length, _) = naive_longest_previous_factor(t, i);
+        assert_eq!(as_usize(lpf[i]), length);
+        let source = as_usize(prev_occ[i]);
+        if length == 0 {
+            assert_eq!(source, i);
+        } else {
+            assert!(source < i);
+            assert_eq!(naive_common_prefix(&t[source..], &t[i..]), length);
+        }
+    }
+}
+
+fn naive_longest_previous_factor(t: &[u8], i: usize) -> (usize, usize) {
+    (0..i)
+        .map(|j| (naive_common_prefix(&t[j..], &t[i..]), j))
+        .max()
+        .unwrap_or((0, i))
+}
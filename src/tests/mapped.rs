@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use memmap2::MmapMut;
+
+use crate::errors::Error;
+use crate::mapped::*;
+use crate::tests::common::*;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("sais-test-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn test_mapped_sais_lcp_32() {
+    let t = random_text(1000..=2000, 0..=3);
+    let (text_path, sa_path, plcp_path, lcp_path) = (temp_path("32.txt"), temp_path("32.sa"), temp_path("32.plcp"), temp_path("32.lcp"));
+    std::fs::write(&text_path, &t).unwrap();
+
+    let text = unsafe { map_input(&text_path) }.unwrap();
+    let mut sa = create_output::<i32, _>(&sa_path, text.len()).unwrap();
+    let mut plcp = create_output::<i32, _>(&plcp_path, text.len()).unwrap();
+    let mut lcp = create_output::<i32, _>(&lcp_path, text.len()).unwrap();
+    crate::sais32::mapped::sais(&text, &mut sa, None).unwrap();
+    crate::sais32::mapped::plcp(&text, &sa, &mut plcp).unwrap();
+    crate::sais32::mapped::lcp(&plcp, &sa, &mut lcp).unwrap();
+    sa.flush().unwrap();
+    lcp.flush().unwrap();
+    drop((sa, plcp, lcp));
+
+    let read = |path: &PathBuf| -> Vec<i32> {
+        std::fs::read(path)
+            .unwrap()
+            .chunks_exact(4)
+            .map(|bytes| i32::from_ne_bytes(bytes.try_into().unwrap()))
+            .collect()
+    };
+    let (expected_sa, expected_lcp) = suffix_and_lcp_arrays_32(&t);
+    assert_eq!(read(&sa_path), expected_sa);
+    assert_eq!(read(&lcp_path), expected_lcp);
+
+    drop(text);
+    for path in [text_path, sa_path, plcp_path, lcp_path] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+#[cfg(feature = "sais64")]
+fn test_mapped_sais_64() {
+    let t = random_text(1000..=2000, 0..=255);
+    let (text_path, sa_path) = (temp_path("64.txt"), temp_path("64.sa"));
+    std::fs::write(&text_path, &t).unwrap();
+
+    let text = unsafe { map_input(&text_path) }.unwrap();
+    // free space after the text is left to the algorithm
+    let mut sa = create_output::<i64, _>(&sa_path, text.len() + 100).unwrap();
+    crate::sais64::mapped::sais(&text, &mut sa, None).unwrap();
+    let sa: Vec<i64> = sa[..text.len() * 8]
+        .chunks_exact(8)
+        .map(|bytes| i64::from_ne_bytes(bytes.try_into().unwrap()))
+        .collect();
+    assert_eq!(sa, suffix_and_lcp_arrays_64(&t).0);
+
+    drop(text);
+    for path in [text_path, sa_path] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn test_mapped_illegal_arguments() {
+    let t = b"mississippi";
+    let mut buffer = MmapMut::map_anon(t.len() * 4 + 4).unwrap();
+
+    // shorter than the text
+    assert_eq!(crate::sais32::mapped::sais(t, &mut buffer[..t.len() * 4 - 4], None), Err(Error::IllegalArguments));
+    // length not a multiple of the index width
+    assert_eq!(crate::sais32::mapped::sais(t, &mut buffer[..t.len() * 4 + 1], None), Err(Error::IllegalArguments));
+    // misaligned
+    assert_eq!(crate::sais32::mapped::sais(t, &mut buffer[1..t.len() * 4 + 1], None), Err(Error::IllegalArguments));
+
+    assert_eq!(crate::sais32::mapped::sais(t, &mut buffer[..t.len() * 4], None), Ok(()));
+    let mut plcp = MmapMut::map_anon(t.len() * 4).unwrap();
+    assert_eq!(crate::sais32::mapped::plcp(t, &buffer[..t.len() * 4], &mut plcp[..t.len() * 4 - 4]), Err(Error::IllegalArguments));
+}
@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+
+use crate::matches::*;
+use crate::tests::common::*;
+
+fn samples() -> Vec<(Vec<u8>, Vec<u8>)> {
+    vec![
+        (b"".to_vec(), b"".to_vec()),
+        (b"abc".to_vec(), b"".to_vec()),
+        (b"abc".to_vec(), b"xyz".to_vec()),
+        (b"xabcdy".to_vec(), b"zzabcdzz".to_vec()),
+        (b"mississippi".to_vec(), b"missouri mississippi".to_vec()),
+        (b"\x00\xff\x00\xff".to_vec(), b"\xff\x00\xff".to_vec()),
+        (random_text(20..=40, 0..=1), random_text(20..=40, 0..=1)),
+        (random_text(20..=40, 0..=3), random_text(20..=40, 0..=3)),
+        (random_text(50..=80, 0..=255), random_text(50..=80, 0..=255)),
+    ]
+}
+
+#[test]
+fn test_longest_common_substring() {
+    for (a, b) in samples() {
+        let gsa = GeneralizedSuffixArray::new(&a, &b).unwrap();
+        let expected = naive_maximal_exact_matches(&a, &b)
+            .into_iter()
+            .map(|m| m.length)
+            .max();
+        let actual = gsa.longest_common_substring();
+        assert_eq!(actual.map(|m| m.length), expected);
+        if let Some(m) = actual {
+            assert_eq!(&a[m.range_a()], &b[m.range_b()]);
+        }
+    }
+}
+
+#[test]
+fn test_common_substrings() {
+    for (a, b) in samples() {
+        let gsa = GeneralizedSuffixArray::new(&a, &b).unwrap();
+        for min_length in [0, 1, 3] {
+            let mut count = 0;
+            for common in gsa.common_substrings(min_length) {
+                assert!(common.length >= Ord::max(min_length, 1));
+                let s = &a[common.positions_a[0]..common.positions_a[0] + common.length];
+                let mut positions_a = common.positions_a.clone();
+                let mut positions_b = common.positions_b.clone();
+                positions_a.sort();
+                positions_b.sort();
+                assert_eq!(positions_a, naive_occurrences(&a, s));
+                assert_eq!(positions_b, naive_occurrences(&b, s));
+                count += 1;
+            }
+            // every maximal exact match is reported as a common substring
+            let lengths: HashSet<usize> = gsa
+                .common_substrings(min_length)
+                .map(|c| c.length)
+                .collect();
+            for m in naive_maximal_exact_matches(&a, &b) {
+                if m.length >= Ord::max(min_length, 1) {
+                    assert!(count > 0 && lengths.contains(&m.length));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_maximal_exact_matches() {
+    for (a, b) in samples() {
+        let gsa = GeneralizedSuffixArray::new(&a, &b).unwrap();
+        for min_length in [0, 2, 4] {
+            let actual: Vec<Match> = gsa.maximal_exact_matches(min_length).collect();
+            let actual_set: HashSet<Match> = actual.iter().copied().collect();
+            assert_eq!(actual.len(), actual_set.len());
+            let expected: HashSet<Match> = naive_maximal_exact_matches(&a, &b)
+                .into_iter()
+                .filter(|m| m.length >= Ord::max(min_length, 1))
+                .collect();
+            assert_eq!(actual_set, expected);
+        }
+    }
+}
+
+fn naive_occurrences(t: &[u8], s: &[u8]) -> Vec<usize> {
+    (0..(t.len() + 1).saturating_sub(s.len()))
+        .filter(|&p| &t[p..p + s.len()] == s)
+        .collect()
+}
+
+fn naive_maximal_exact_matches(a: &[u8], b: &[u8]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for i in 0..a.len() {
+        for j in 0..b.len() {
+            if i > 0 && j > 0 && a[i - 1] == b[j - 1] {
+                continue;
+            }
+            let length = naive_common_prefix(&a[i..], &b[j..]);
+            if length > 0 {
+                matches.push(Match { a: i, b: j, length });
+            }
+        }
+    }
+    matches
+}
@@ -1,8 +1,10 @@
 mod common;
 
-#[cfg(feature = "bwt_aux")]
 mod aux_index;
 
+#[cfg(feature = "sais32")]
+mod rmq;
+
 #[cfg(feature = "sais16")]
 mod sais16;
 
@@ -11,3 +13,62 @@ mod sais32;
 
 #[cfg(feature = "sais64")]
 mod sais64;
+
+#[cfg(feature = "sais32")]
+mod lce;
+
+#[cfg(feature = "sais32")]
+mod esa;
+
+#[cfg(feature = "sais32")]
+mod repeats;
+
+#[cfg(feature = "sais32")]
+mod matches;
+
+#[cfg(any(feature = "sais32", feature = "sais64"))]
+mod lz77;
+
+mod lyndon;
+
+#[cfg(feature = "sais32")]
+mod ebwt;
+
+#[cfg(feature = "sais32")]
+mod compress;
+
+#[cfg(feature = "sais32")]
+mod stream;
+
+#[cfg(any(feature = "sais16", feature = "sais32"))]
+mod extract;
+
+#[cfg(any(feature = "sais16", feature = "sais32"))]
+mod sentinel;
+
+#[cfg(feature = "sais32")]
+mod alphabet;
+
+#[cfg(feature = "sais32")]
+mod unicode;
+
+#[cfg(feature = "sais32")]
+mod sparse;
+
+#[cfg(feature = "sais32")]
+mod token;
+
+#[cfg(any(feature = "sais32", feature = "sais64"))]
+mod csa;
+
+#[cfg(feature = "sais32")]
+mod cst;
+
+#[cfg(all(feature = "mmap", feature = "sais32"))]
+mod file;
+
+#[cfg(all(feature = "serde", feature = "sais32"))]
+mod serde;
+
+#[cfg(all(feature = "mmap", feature = "sais32"))]
+mod mapped;
@@ -0,0 +1,98 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::esa::EnhancedSuffixArray;
+use crate::repeats::*;
+use crate::tests::common::*;
+
+#[test]
+fn test_maximal_repeats() {
+    for t in TEXTS.iter() {
+        let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+        let esa = EnhancedSuffixArray::new(sa.as_slice(), lcp.as_slice()).unwrap();
+        let expected = naive_maximal_repeats(t);
+        for (min_length, min_occurrences) in [(0, 0), (2, 2), (3, 3)] {
+            let actual: HashSet<(Vec<u8>, usize)> = maximal_repeats(t, &esa, min_length, min_occurrences)
+                .unwrap()
+                .map(|r| (t[r.first_occurrence(&sa)].to_vec(), r.occurrences()))
+                .collect();
+            let filtered: HashSet<(Vec<u8>, usize)> = expected
+                .iter()
+                .filter(|(s, occ)| s.len() >= min_length && *occ >= min_occurrences)
+                .cloned()
+                .collect();
+            assert_eq!(actual, filtered);
+        }
+    }
+}
+
+#[test]
+fn test_supermaximal_repeats() {
+    for t in TEXTS.iter() {
+        let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+        let esa = EnhancedSuffixArray::new(sa.as_slice(), lcp.as_slice()).unwrap();
+        let maximal = naive_maximal_repeats(t);
+        let expected: HashSet<(Vec<u8>, usize)> = maximal
+            .iter()
+            .filter(|(s, _)| {
+                !maximal
+                    .iter()
+                    .any(|(other, _)| other.len() > s.len() && other.windows(s.len()).any(|w| w == s.as_slice()))
+            })
+            .cloned()
+            .collect();
+        let actual: HashSet<(Vec<u8>, usize)> = supermaximal_repeats(t, &esa, 0, 0)
+            .unwrap()
+            .map(|r| (t[r.first_occurrence(&sa)].to_vec(), r.occurrences()))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn test_longest_repeated_substring() {
+    for t in TEXTS.iter() {
+        let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+        let esa = EnhancedSuffixArray::new(sa.as_slice(), lcp.as_slice()).unwrap();
+        let expected = naive_maximal_repeats(t)
+            .into_iter()
+            .map(|(s, _)| s.len())
+            .max();
+        match longest_repeated_substring(&esa) {
+            Some(r) => {
+                assert_eq!(Some(r.length), expected);
+                let s = &t[r.first_occurrence(&sa)];
+                let mut positions: Vec<usize> = r.positions(&sa).collect();
+                positions.sort();
+                let naive: Vec<usize> = (0..=t.len() - s.len())
+                    .filter(|&p| &t[p..p + s.len()] == s)
+                    .collect();
+                assert_eq!(positions, naive);
+            }
+            None => assert_eq!(expected, None),
+        }
+    }
+}
+
+fn naive_maximal_repeats(t: &[u8]) -> HashSet<(Vec<u8>, usize)> {
+    let mut occurrences: HashMap<&[u8], Vec<usize>> = HashMap::new();
+    for i in 0..t.len() {
+        for j in i + 1..=t.len() {
+            occurrences.entry(&t[i..j]).or_default().push(i);
+        }
+    }
+    occurrences
+        .into_iter()
+        .filter(|(s, positions)| {
+            let left: HashSet<Option<u8>> = positions
+                .iter()
+                .map(|&p| p.checked_sub(1).map(|q| t[q]))
+                .collect();
+            let right: HashSet<Option<u8>> = positions
+                .iter()
+                .map(|&p| t.get(p + s.len()).copied())
+                .collect();
+            positions.len() >= 2 && (left.len() > 1 || left.contains(&None)) && (right.len() > 1 || right.contains(&None))
+        })
+        .map(|(s, positions)| (s.to_vec(), positions.len()))
+        .collect()
+}
@@ -0,0 +1,47 @@
+use crate::rmq::*;
+use crate::tests::common::*;
+
+#[test]
+fn test_range_minimum() {
+    for values in [
+        vec![],
+        vec![7],
+        random_text(1..=100, 0..=3),
+        random_text(300..=600, 0..=1000),
+        random_text(300..=600, 0..=3),
+    ] {
+        let rmq = RangeMinimum::new(values.clone());
+        for start in 0..values.len() {
+            for end in start + 1..=values.len() {
+                let expected = (start..end).min_by_key(|&i| (values[i], i)).unwrap();
+                assert_eq!(rmq.argmin(start..end), expected);
+                assert_eq!(rmq.min(start..end), values[expected]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_block_range_minimum() {
+    for values in [
+        vec![],
+        vec![7],
+        random_text(1..=100, 0..=3),
+        random_text(300..=600, 0..=1000),
+        random_text(300..=600, 0..=3),
+    ] {
+        let rmq = BlockRangeMinimum::new(&values);
+        for start in 0..values.len() {
+            for end in start + 1..=values.len() {
+                let expected = (start..end).min_by_key(|&i| (values[i], i)).unwrap();
+                assert_eq!(rmq.argmin(start..end), expected);
+                assert_eq!(rmq.min(start..end), values[expected]);
+            }
+        }
+        for k in 0..values.len() {
+            let smaller = |&i: &usize| values[i] < values[k];
+            assert_eq!(rmq.find_last(0..k, |v| v < values[k]), (0..k).rev().find(smaller));
+            assert_eq!(rmq.find_first(k + 1..values.len(), |v| v < values[k]), (k + 1..values.len()).find(smaller));
+        }
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(unused)]
 
 use once_cell::sync::Lazy;
+use rand::prelude::*;
 
 use crate::sais32::*;
 use crate::tests::common::*;
@@ -419,3 +420,145 @@ fn test_plcp_lcp_parallel() {
         check_lcp_array(t, sa.as_slice(), lcp_array.as_slice());
     }
 }
+
+#[test]
+fn test_bwt_unbwt_cyclic() {
+    let mut texts: Vec<Vec<u8>> = TEXTS
+        .iter()
+        .filter(|item| item.len() < 1000)
+        .cloned()
+        .collect();
+    texts.extend([
+        b"abababab".to_vec(),
+        b"aaaa".to_vec(),
+        b"abcabcabd".to_vec(),
+        b"babbabbab".to_vec(),
+    ]);
+    for repeat in [1, 1, 2, 3, 4].repeat(4) {
+        texts.push(random_text(1..=8, 0..=1).repeat(repeat));
+    }
+    for t in texts.iter() {
+        let mut u = vec![0u8; t.len()];
+        let mut s = vec![0u8; t.len()];
+
+        // bwt_cyclic + unbwt_cyclic
+        let i = bwt_cyclic(t, u.as_mut_slice()).expect("bwt failed");
+        check_cyclic_bwt(t, u.as_slice(), i as usize);
+        unbwt_cyclic(u.as_slice(), s.as_mut_slice(), i).expect("unbwt failed");
+        assert_eq!(t, &s);
+    }
+    assert_eq!(unbwt_cyclic(&[0, 1], &mut [0; 2], 2), Err(Error::IllegalArguments));
+    assert_eq!(unbwt_cyclic(&[], &mut [], 1), Err(Error::IllegalArguments));
+    assert_eq!(unbwt_cyclic(&[], &mut [], 0), Ok(()));
+}
+
+#[test]
+fn test_bbwt_unbbwt() {
+    let mut texts: Vec<Vec<u8>> = TEXTS
+        .iter()
+        .filter(|item| item.len() < 1000)
+        .cloned()
+        .collect();
+    texts.extend([
+        b"abababab".to_vec(),
+        b"babb".to_vec(),
+        b"abcabcabd".to_vec(),
+        b"ab ab ab ba".to_vec(),
+    ]);
+    texts.push(random_text(100..=200, 0..=1));
+    for _ in 0..100 {
+        texts.push(random_text(1..=12, 0..=2));
+        texts.push(random_text(1..=4, 0..=1).repeat(4));
+    }
+
+    // large texts, whose rotations are sorted through several recursion levels
+    let mut rng = thread_rng();
+    texts.push(random_text(10000..=20000, 0..=1));
+    texts.push(random_text(10000..=20000, 0..=3));
+    texts.push(random_text(10000..=20000, 0..=255));
+    texts.push(b"a".repeat(10000));
+    texts.push(random_text(20..=50, 0..=1).repeat(500));
+    let mut mutated = random_text(20..=50, 0..=3).repeat(500);
+    for _ in 0..10 {
+        let k = rng.gen_range(0..mutated.len());
+        mutated[k] = rng.gen_range(0..=3);
+    }
+    texts.push(mutated);
+    let (mut fibonacci, mut previous) = (b"ab".to_vec(), b"a".to_vec());
+    while fibonacci.len() < 10000 {
+        (fibonacci, previous) = ([fibonacci.as_slice(), previous.as_slice()].concat(), fibonacci);
+    }
+    texts.push(fibonacci);
+    for t in texts.iter() {
+        let mut u = vec![0u8; t.len()];
+        let mut s = vec![0u8; t.len()];
+
+        // bbwt + unbbwt
+        bbwt(t, u.as_mut_slice()).expect("bbwt failed");
+        assert_eq!(u, naive_bbwt(t));
+        unbbwt(u.as_slice(), s.as_mut_slice()).expect("unbbwt failed");
+        assert_eq!(t, &s);
+    }
+}
+
+#[test]
+fn test_bwt_from_sa_sa_from_bwt() {
+    for t in TEXTS.iter() {
+        let mut sa = vec![0i32; t.len()];
+        let mut a = vec![0i32; t.len()];
+        let mut u0 = vec![0u8; t.len()];
+        let mut u1 = vec![0u8; t.len()];
+        sais(t, sa.as_mut_slice(), None).expect("sais failed");
+
+        // bwt_from_sa agrees with bwt
+        let i0 = bwt(t, u0.as_mut_slice(), a.as_mut_slice(), None).expect("bwt failed");
+        let i1 = bwt_from_sa(t, sa.as_slice(), u1.as_mut_slice()).expect("bwt_from_sa failed");
+        assert_eq!((i0, &u0), (i1, &u1));
+
+        // bwt_aux_from_sa agrees with bwt_aux
+        for mut i0 in allocate_aux_arrays(t.len()) {
+            let mut i1 = i0.clone();
+            bwt_aux(t, u0.as_mut_slice(), a.as_mut_slice(), None, i0.as_mut_slice()).expect("bwt failed");
+            bwt_aux_from_sa(t, sa.as_slice(), u1.as_mut_slice(), i1.as_mut_slice()).expect("bwt_from_sa failed");
+            assert_eq!((&i0, &u0), (&i1, &u1));
+        }
+
+        // sa_from_bwt recovers the suffix array
+        sa_from_bwt(u1.as_slice(), a.as_mut_slice(), i1).expect("sa_from_bwt failed");
+        assert_eq!(a, sa);
+    }
+    assert_eq!(bwt_from_sa(&[0, 1], &[0, 0], &mut [0; 2]), Err(Error::IllegalArguments));
+    assert_eq!(bwt_from_sa(&[0, 1], &[0, 2], &mut [0; 2]), Err(Error::IllegalArguments));
+    assert_eq!(sa_from_bwt(&[0, 1], &mut [0; 2], 3), Err(Error::IllegalArguments));
+}
+
+#[test]
+fn test_sa_and_bwt() {
+    for t in TEXTS.iter() {
+        let mut a = vec![0i32; t.len()];
+        let mut u0 = vec![0u8; t.len()];
+        let mut u1 = vec![0u8; t.len()];
+        let i0 = bwt(t, u0.as_mut_slice(), a.as_mut_slice(), None).expect("bwt failed");
+        for mut sa in allocate_suffix_arrays(t.len()) {
+            // sa_and_bwt
+            let i1 = sa_and_bwt(t, sa.as_mut_slice(), u1.as_mut_slice(), None).expect("sa_and_bwt failed");
+            check_suffix_array(t, &sa[..t.len()]);
+            assert_eq!((i0, &u0), (i1, &u1));
+
+            // sa_and_bwt, w/ output symbol frequency table
+            let mut freq = vec![0i32; FREQ_TABLE_SIZE];
+            let i1 = sa_and_bwt(t, sa.as_mut_slice(), u1.as_mut_slice(), Some(&mut freq)).expect("sa_and_bwt failed");
+            check_frequency_table(t, freq.as_slice(), FREQ_TABLE_SIZE);
+            assert_eq!((i0, &u0), (i1, &u1));
+
+            // parallel::sa_and_bwt
+            #[cfg(feature = "parallel")]
+            {
+                let i1 = parallel::sa_and_bwt(t, sa.as_mut_slice(), u1.as_mut_slice(), None, 0).expect("sa_and_bwt failed");
+                check_suffix_array(t, &sa[..t.len()]);
+                assert_eq!((i0, &u0), (i1, &u1));
+            }
+        }
+    }
+    assert_eq!(sa_and_bwt(&[0, 1], &mut [0; 2], &mut [0; 3], None), Err(Error::IllegalArguments));
+}
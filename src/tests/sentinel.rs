@@ -0,0 +1,96 @@
+use crate::errors::Error;
+use crate::sentinel::*;
+use crate::tests::common::*;
+
+fn naive_bwt_with_sentinel<T: Ord + Copy>(t: &[T]) -> Vec<Option<T>> {
+    // suffixes of t$ in sorted order, where None is the sentinel smaller than all symbols
+    let text: Vec<Option<T>> = t.iter().copied().map(Some).chain([None]).collect();
+    let mut suffixes: Vec<usize> = (0..text.len()).collect();
+    suffixes.sort_by(|&p, &q| text[p..].cmp(&text[q..]));
+    suffixes
+        .into_iter()
+        .map(|p| if p == 0 { None } else { text[p - 1] })
+        .collect()
+}
+
+#[test]
+#[cfg(feature = "sais32")]
+fn test_sentinel_32() {
+    use crate::sais32::*;
+
+    let texts = [
+        b"".to_vec(),
+        b"banana".to_vec(),
+        b"mississippi".to_vec(),
+        random_text(100..=200, 0..=3),
+        random_text(100..=200, 0..=255),
+    ];
+    for t in texts.iter() {
+        let mut u = vec![0u8; t.len()];
+        let mut a = vec![0i32; t.len()];
+        let i = bwt(t, u.as_mut_slice(), a.as_mut_slice(), None).expect("bwt failed");
+        let expected = naive_bwt_with_sentinel(t);
+
+        // widened into u16
+        let mut widened = vec![0u16; t.len() + 1];
+        with_sentinel(&u, i, &Widened, &mut widened).expect("with_sentinel failed");
+        let decoded: Vec<Option<u8>> = widened.iter().map(|&s| Widened.decode(s)).collect();
+        assert_eq!(decoded, expected);
+        let mut v = vec![0u8; t.len()];
+        assert_eq!(without_sentinel(&widened, &Widened, &mut v), Ok(i));
+        assert_eq!(v, u);
+
+        // reserved symbol, if absent from the text
+        if let Some(reserved) = (0..=255).find(|c| !t.contains(c)) {
+            let mut reserved_bwt = vec![0u8; t.len() + 1];
+            with_sentinel(&u, i, &Reserved(reserved), &mut reserved_bwt).expect("with_sentinel failed");
+            assert_eq!(
+                reserved_bwt,
+                expected
+                    .iter()
+                    .map(|s| s.unwrap_or(reserved))
+                    .collect::<Vec<_>>()
+            );
+            let mut v = vec![0u8; t.len()];
+            assert_eq!(without_sentinel(&reserved_bwt, &Reserved(reserved), &mut v), Ok(i));
+            let mut s = vec![0u8; t.len()];
+            unbwt(&v, &mut s, &mut vec![0; t.len() + 1], None, i).expect("unbwt failed");
+            assert_eq!(&s, t);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "sais16")]
+fn test_sentinel_16() {
+    use crate::sais16::*;
+
+    let t: Vec<u16> = random_text(100..=200, 0..=u16::MAX);
+    let mut u = vec![0u16; t.len()];
+    let mut a = vec![0i32; t.len()];
+    let i = bwt(&t, u.as_mut_slice(), a.as_mut_slice(), None).expect("bwt failed");
+
+    let mut widened = vec![0u32; t.len() + 1];
+    with_sentinel(&u, i, &Widened, &mut widened).expect("with_sentinel failed");
+    let decoded: Vec<Option<u16>> = widened.iter().map(|&s| Widened.decode(s)).collect();
+    assert_eq!(decoded, naive_bwt_with_sentinel(&t));
+    let mut v = vec![0u16; t.len()];
+    assert_eq!(without_sentinel(&widened, &Widened, &mut v), Ok(i));
+    assert_eq!(v, u);
+}
+
+#[test]
+fn test_sentinel_illegal_arguments() {
+    let u = b"annbaa";
+    let mut out = vec![0u8; 7];
+    assert_eq!(with_sentinel(u, 0i32, &Reserved(b'$'), &mut out), Err(Error::IllegalArguments));
+    assert_eq!(with_sentinel(u, 7i32, &Reserved(b'$'), &mut out), Err(Error::IllegalArguments));
+    assert_eq!(with_sentinel(u, 4i32, &Reserved(b'a'), &mut out), Err(Error::IllegalArguments));
+    assert_eq!(with_sentinel(u, 4i32, &Reserved(b'$'), &mut out[..6]), Err(Error::IllegalArguments));
+
+    let mut v = vec![0u8; 6];
+    assert_eq!(without_sentinel::<_, i32, _>(b"annbaaa", &Reserved(b'$'), &mut v), Err(Error::IllegalArguments));
+    assert_eq!(without_sentinel::<_, i32, _>(b"$nnbaaa", &Reserved(b'$'), &mut v), Err(Error::IllegalArguments));
+    assert_eq!(without_sentinel::<_, i32, _>(b"ann$$aa", &Reserved(b'$'), &mut v), Err(Error::IllegalArguments));
+    assert_eq!(without_sentinel::<_, i32, _>(&[1u16, 2, 0, 300], &Widened, &mut v[..3]), Err(Error::IllegalArguments));
+}
@@ -0,0 +1,205 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::alphabet::Alphabet;
+use crate::csa::CompressedSuffixArray;
+use crate::ebwt::ebwt;
+use crate::errors::Error;
+use crate::esa::{EnhancedSuffixArray, LcpInterval};
+use crate::extract::Bwt;
+use crate::lz77::{longest_previous_factor, lz77};
+use crate::repeats::longest_repeated_substring;
+use crate::sparse::SparseSuffixArray;
+use crate::tests::common::*;
+use crate::token::{TokenSuffixArray, Vocabulary, Whitespace};
+use crate::unicode::StrSuffixArray;
+
+fn round_trip<V: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(value: &V) {
+    let json = serde_json::to_string(value).expect("json serialization failed");
+    assert_eq!(&serde_json::from_str::<V>(&json).expect("json deserialization failed"), value);
+    let bytes = bincode::serialize(value).expect("bincode serialization failed");
+    assert_eq!(&bincode::deserialize::<V>(&bytes).expect("bincode deserialization failed"), value);
+}
+
+#[test]
+fn test_serde_errors() {
+    round_trip(&Error::<i32>::IllegalArguments);
+    round_trip(&Error::<i32>::InternalError);
+    round_trip(&Error::<i32>::Uncategorized(-7));
+    assert_eq!(serde_json::to_string(&Error::<i32>::IllegalArguments).unwrap(), "\"IllegalArguments\"");
+}
+
+#[test]
+fn test_serde_results() {
+    let t = b"abracadabra abracadabra";
+    let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+
+    round_trip(&ebwt(&[&b"banana"[..], b"ananas"]).unwrap());
+    round_trip(&longest_previous_factor(&sa, &lcp).unwrap());
+    round_trip(&lz77(t, &sa, &lcp).unwrap());
+    round_trip(&Alphabet::new(t));
+
+    let esa = EnhancedSuffixArray::new(&sa, &lcp).unwrap();
+    let intervals: Vec<LcpInterval> = esa.intervals().collect();
+    round_trip(&intervals);
+    for (x, y) in intervals
+        .iter()
+        .zip(serde_json::from_str::<Vec<LcpInterval>>(&serde_json::to_string(&intervals).unwrap()).unwrap())
+    {
+        assert!(x.children().eq(y.children()));
+    }
+    round_trip(&longest_repeated_substring(&esa));
+    round_trip(&Bwt::new(t, 4, crate::sais32::bwt_aux).unwrap());
+}
+
+#[test]
+fn test_serde_compressed_suffix_array() {
+    let t = random_text(200..=400, 0..=3);
+    let (sa, _) = suffix_and_lcp_arrays_32(&t);
+    let csa = CompressedSuffixArray::new(&t, &sa, 8).unwrap();
+    let bytes = bincode::serialize(&csa).unwrap();
+    let csa: CompressedSuffixArray<i32> = bincode::deserialize(&bytes).unwrap();
+    for (i, &p) in sa.iter().enumerate() {
+        assert_eq!(csa.lookup(i), p as usize);
+    }
+    assert_eq!(csa.extract(0..t.len()), t);
+}
+
+#[test]
+fn test_serde_compressed_suffix_array_invalid() {
+    let t = random_text(200..=400, 0..=3);
+    let (sa, _) = suffix_and_lcp_arrays_32(&t);
+    let json = serde_json::to_value(CompressedSuffixArray::new(&t, &sa, 8).unwrap()).unwrap();
+    let rejects = |field: &str, value: serde_json::Value| {
+        let mut json = json.clone();
+        json[field] = value;
+        assert!(serde_json::from_value::<CompressedSuffixArray<i32>>(json).is_err(), "{} accepted", field);
+    };
+    rejects("rate", 0.into());
+    rejects("n", (t.len() + 1).into());
+    rejects("first", serde_json::json!(vec![0; 257]));
+    rejects("psi_deltas", serde_json::json!([]));
+    rejects("sa_samples", serde_json::json!(vec![0; t.len() / 8 + 1]));
+    rejects("isa_samples", serde_json::json!(vec![1; t.len() / 8 + 1]));
+    rejects("sampled", serde_json::json!(vec![u64::MAX; (t.len() + 1).div_ceil(64)]));
+
+    // corrupted encodings are either rejected or consistent
+    let bytes = bincode::serialize(&CompressedSuffixArray::new(&t, &sa, 8).unwrap()).unwrap();
+    for k in 0..bytes.len() {
+        let mut corrupted = bytes.clone();
+        corrupted[k] ^= 1 << (k % 8);
+        if let Ok(csa) = bincode::deserialize::<CompressedSuffixArray<i32>>(&corrupted) {
+            let text = csa.extract(0..csa.len());
+            for i in 0..csa.len() {
+                let p = csa.lookup(i);
+                assert_eq!(csa.inverse(p), i);
+                assert!(i == 0 || text[csa.lookup(i - 1)..] < text[p..]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_serde_invalid() {
+    let interval = |json: &str| serde_json::from_str::<LcpInterval>(json).is_ok();
+    assert!(interval(r#"{"lcp":1,"range":{"start":2,"end":6},"boundaries":[3,5]}"#));
+    assert!(!interval(r#"{"lcp":1,"range":{"start":2,"end":6},"boundaries":[5,3]}"#));
+    assert!(!interval(r#"{"lcp":1,"range":{"start":2,"end":6},"boundaries":[2,5]}"#));
+    assert!(!interval(r#"{"lcp":1,"range":{"start":2,"end":6},"boundaries":[3,6]}"#));
+    assert!(!interval(r#"{"lcp":1,"range":{"start":6,"end":2},"boundaries":[]}"#));
+
+    let vocabulary = |json: &str| serde_json::from_str::<Vocabulary>(json).is_ok();
+    assert!(vocabulary(r#"{"tokens":["a","b"]}"#));
+    assert!(!vocabulary(r#"{"tokens":["b","a"]}"#));
+    assert!(!vocabulary(r#"{"tokens":["a","a"]}"#));
+
+    let alphabet = |json: &str| serde_json::from_str::<Alphabet<u32>>(json).is_ok();
+    assert!(alphabet(r#"[1,5,7]"#));
+    assert!(alphabet(r#"[]"#));
+    assert!(!alphabet(r#"[5,1,7]"#));
+    assert!(!alphabet(r#"[1,5,5]"#));
+
+    let str_sa = |json: &str| serde_json::from_str::<StrSuffixArray>(json).is_ok();
+    assert!(str_sa(r#"{"text":"aé","sa":[0,1]}"#));
+    assert!(!str_sa(r#"{"text":"aé","sa":[0,2]}"#));
+    assert!(!str_sa(r#"{"text":"aé","sa":[0,0]}"#));
+    assert!(!str_sa(r#"{"text":"aé","sa":[3]}"#));
+    assert!(!str_sa(r#"{"text":"aé","sa":[-1]}"#));
+
+    let text = "the cat sat on the mat";
+    let tsa = TokenSuffixArray::new(text, Whitespace).unwrap();
+    let json = serde_json::to_value(&tsa).unwrap();
+    let rejects = |field: &str, value: serde_json::Value| {
+        let mut json = json.clone();
+        json[field] = value;
+        let json = serde_json::to_string(&json).unwrap();
+        assert!(serde_json::from_str::<TokenSuffixArray<Whitespace>>(&json).is_err(), "{} accepted", field);
+    };
+    rejects("text", "the dog sat on the mat".into());
+    rejects("ids", serde_json::json!([0, 0, 0, 0, 0, 0]));
+    rejects("sa", serde_json::json!([0, 0, 1, 2, 3, 4]));
+    rejects("tokens", serde_json::json!([]));
+
+    // bincode encodes structs as tuples of their fields
+    let sparse = |sa: Vec<i32>, lcp: Option<Vec<i32>>| {
+        let bytes = bincode::serialize(&(&b"abracadabra"[..], sa, lcp)).unwrap();
+        bincode::deserialize::<SparseSuffixArray>(&bytes).is_ok()
+    };
+    assert!(sparse(vec![10, 7, 0], Some(vec![0, 1, 4])));
+    assert!(sparse(vec![10, 7, 0], None));
+    assert!(!sparse(vec![11, 7, 0], None));
+    assert!(!sparse(vec![7, 7, 0], None));
+    assert!(!sparse(vec![10, 7, 0], Some(vec![0, 1])));
+    assert!(!sparse(vec![10, 7, 0], Some(vec![1, 1, 4])));
+}
+
+#[test]
+fn test_serde_borrowed() {
+    let text = "naïve café, naïve";
+    let sa = StrSuffixArray::new(text).unwrap();
+    let json = serde_json::to_string(&sa).unwrap();
+    let loaded: StrSuffixArray = serde_json::from_str(&json).unwrap();
+    assert_eq!((loaded.text(), loaded.suffix_array()), (sa.text(), sa.suffix_array()));
+    let bytes = bincode::serialize(&sa).unwrap();
+    let loaded: StrSuffixArray = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(loaded.count("ïve"), 2);
+
+    let text = b"to be or not to be";
+    let ssa = SparseSuffixArray::new(text, &[0, 3, 6, 9, 13, 16], true).unwrap();
+    let bytes = bincode::serialize(&ssa).unwrap();
+    let loaded: SparseSuffixArray = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(loaded.text(), text);
+    assert_eq!(loaded.suffix_array(), ssa.suffix_array());
+    assert_eq!(loaded.lcp_array(), ssa.lcp_array());
+
+    let text = "the cat sat on the mat, the cat ran";
+    let tsa = TokenSuffixArray::new(text, Whitespace).unwrap();
+    let json = serde_json::to_string(&tsa).unwrap();
+    let loaded: TokenSuffixArray<Whitespace> = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.vocabulary(), tsa.vocabulary());
+    assert_eq!(loaded.suffix_array(), tsa.suffix_array());
+    assert_eq!(loaded.find("the cat"), tsa.find("the cat"));
+    let bytes = bincode::serialize(&tsa).unwrap();
+    let loaded: TokenSuffixArray<Whitespace> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(loaded.count("the cat"), 2);
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn test_serde_index() {
+    use crate::file::Index;
+
+    let t = b"mississippi";
+    let (sa, lcp) = suffix_and_lcp_arrays_32(t);
+    let index = Index {
+        text: Some(&t[..]),
+        sa: Some(&sa),
+        lcp: Some(&lcp),
+        ..Index::new(t.len())
+    };
+    let json = serde_json::to_value(index).unwrap();
+    assert_eq!(json["len"], t.len());
+    assert_eq!(serde_json::from_value::<Vec<u8>>(json["text"].clone()).unwrap(), t);
+    assert_eq!(serde_json::from_value::<Vec<i32>>(json["sa"].clone()).unwrap(), sa);
+    assert_eq!(serde_json::from_value::<Option<Vec<i32>>>(json["aux"].clone()).unwrap(), None);
+}
@@ -0,0 +1,75 @@
+use crate::errors::Error;
+use crate::sparse::*;
+use crate::tests::common::*;
+
+fn texts() -> Vec<Vec<u8>> {
+    vec![
+        b"".to_vec(),
+        b"to be or not to be".to_vec(),
+        b"aaaaaaaaaa".to_vec(),
+        random_text(200..=400, 0..=1),
+        random_text(200..=400, 0..=3),
+        random_text(200..=400, 0..=255),
+    ]
+}
+
+fn check_sparse_suffix_array(t: &[u8], positions: &[usize], ssa: &SparseSuffixArray) {
+    let mut expected = positions.to_vec();
+    expected.sort_by(|&p, &q| t[p..].cmp(&t[q..]));
+    expected.dedup();
+    let actual: Vec<usize> = ssa.suffix_array().iter().map(|&p| p as usize).collect();
+    assert_eq!(actual, expected);
+
+    let lcp = ssa.lcp_array().expect("lcp array missing");
+    assert_eq!(lcp.len(), expected.len());
+    for k in 1..expected.len() {
+        assert_eq!(lcp[k] as usize, naive_common_prefix(&t[expected[k - 1]..], &t[expected[k]..]));
+    }
+}
+
+#[test]
+fn test_sparse_suffix_array() {
+    for t in texts() {
+        let every_third: Vec<usize> = (0..t.len())
+            .rev()
+            .step_by(3)
+            .chain([0, 0])
+            .filter(|&p| p < t.len())
+            .collect();
+        let ssa = SparseSuffixArray::new(&t, &every_third, true).unwrap();
+        check_sparse_suffix_array(&t, &every_third, &ssa);
+
+        let after_zero: Vec<usize> = (0..t.len()).filter(|&p| p == 0 || t[p - 1] == 0).collect();
+        let ssa = SparseSuffixArray::from_predicate(&t, |p| p == 0 || t[p - 1] == 0, true).unwrap();
+        check_sparse_suffix_array(&t, &after_zero, &ssa);
+
+        let ssa = SparseSuffixArray::from_predicate(&t, |p| p % 2 == 1, false).unwrap();
+        assert_eq!(ssa.lcp_array(), None);
+        assert_eq!(ssa.len(), t.len() / 2);
+    }
+}
+
+#[test]
+fn test_sparse_find() {
+    for t in texts() {
+        let ssa = SparseSuffixArray::from_predicate(&t, |p| p % 3 == 0, false).unwrap();
+        for length in 0..4 {
+            for start in (0..t.len().saturating_sub(length)).step_by(7) {
+                let pattern = &t[start..start + length];
+                let mut actual: Vec<usize> = ssa.find(pattern).collect();
+                actual.sort();
+                let expected: Vec<usize> = (0..t.len())
+                    .filter(|&p| p % 3 == 0 && t[p..].starts_with(pattern))
+                    .collect();
+                assert_eq!(actual, expected);
+                assert_eq!(ssa.count(pattern), expected.len());
+            }
+        }
+        assert_eq!(ssa.count(b"\xff\xff\xff\xff\xff\xff\xff\xff"), 0);
+    }
+}
+
+#[test]
+fn test_sparse_illegal_arguments() {
+    assert_eq!(SparseSuffixArray::new(b"abc", &[0, 3], false).err(), Some(Error::IllegalArguments));
+}
@@ -0,0 +1,168 @@
+use std::io::{ErrorKind, Read, Write};
+
+use crate::stream::*;
+use crate::tests::common::*;
+
+fn encode(data: &[u8], block_size: usize, chunk_size: usize) -> Vec<u8> {
+    let mut writer = BwtWriter::new(Vec::new(), block_size).unwrap();
+    for chunk in data.chunks(chunk_size) {
+        writer.write_all(chunk).unwrap();
+    }
+    writer.finish().unwrap()
+}
+
+fn decode(encoded: &[u8], chunk_size: usize) -> std::io::Result<Vec<u8>> {
+    let mut reader = BwtReader::new(encoded)?;
+    let mut decoded = Vec::new();
+    let mut buf = vec![0; chunk_size];
+    loop {
+        match reader.read(&mut buf)? {
+            0 => return Ok(decoded),
+            n => decoded.extend_from_slice(&buf[..n]),
+        }
+    }
+}
+
+#[test]
+fn test_bwt_writer_reader() {
+    let samples = [
+        b"".to_vec(),
+        b"mississippi".to_vec(),
+        random_text(1000..=2000, 0..=3),
+        random_text(1000..=2000, 0..=255),
+    ];
+    for data in samples {
+        for block_size in [1, 5, 64, 1000, DEFAULT_BLOCK_SIZE] {
+            for chunk_size in [1, 7, 4096] {
+                let encoded = encode(&data, block_size, chunk_size);
+                let blocks = data.len().div_ceil(block_size);
+                assert_eq!(encoded.len(), 8 + blocks * 8 + data.len() + 4);
+                assert_eq!(decode(&encoded, chunk_size).unwrap(), data);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bwt_writer_flush_and_drop() {
+    let mut encoded = Vec::new();
+    {
+        let mut writer = BwtWriter::new(&mut encoded, 100).unwrap();
+        writer.write_all(b"banana").unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.get_ref().len(), 8 + 8 + 6);
+        writer.write_all(b"bandana").unwrap();
+    }
+    assert_eq!(encoded.len(), 8 + 8 + 6 + 8 + 7 + 4);
+    assert_eq!(decode(&encoded, 3).unwrap(), b"bananabandana");
+}
+
+#[test]
+fn test_bwt_reader_invalid_data() {
+    let encoded = encode(b"abracadabra", 4, 11);
+    assert_eq!(
+        BwtReader::new(&b"SBWZ\x04\x00\x00\x00"[..])
+            .err()
+            .unwrap()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(
+        BwtReader::new(&b"SBWT\x00\x00\x00\x00"[..])
+            .err()
+            .unwrap()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+    for n in 0..encoded.len() {
+        assert!(decode(&encoded[..n], 16).is_err());
+    }
+    let mut corrupted = encoded.clone();
+    corrupted[8] = 5;
+    assert_eq!(decode(&corrupted, 16).unwrap_err().kind(), ErrorKind::InvalidData);
+    let mut corrupted = encoded.clone();
+    corrupted[12] = 0;
+    assert_eq!(decode(&corrupted, 16).unwrap_err().kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_bwt_reader_declared_length() {
+    // a block declared as large as possible, followed by a few bytes only
+    let encoded = b"SBWT\xff\xff\xff\x7f\xff\xff\xff\x7f\x01\x00\x00\x00abc";
+    assert_eq!(decode(encoded, 16).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+}
+
+/// Writer failing once when `fail_at` bytes are written, then accepting everything.
+struct FlakyWriter {
+    written: Vec<u8>,
+    fail_at: Option<usize>,
+}
+
+impl Write for FlakyWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = match self.fail_at {
+            Some(at) => buf.len().min(at - self.written.len()),
+            None => buf.len(),
+        };
+        if n == 0 && !buf.is_empty() {
+            self.fail_at = None;
+            return Err(ErrorKind::Other.into());
+        }
+        self.written.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_bwt_writer_poisoned() {
+    // finish fails halfway through the block, drop must not write it again
+    let mut inner = FlakyWriter {
+        written: Vec::new(),
+        fail_at: Some(8 + 4),
+    };
+    let mut writer = BwtWriter::new(&mut inner, 100).unwrap();
+    writer.write_all(b"banana").unwrap();
+    assert!(writer.finish().is_err());
+    assert_eq!(inner.written.len(), 8 + 4);
+
+    // finish fails in the trailer, drop must not write another one
+    let mut inner = FlakyWriter {
+        written: Vec::new(),
+        fail_at: Some(8 + 8 + 6 + 2),
+    };
+    let mut writer = BwtWriter::new(&mut inner, 100).unwrap();
+    writer.write_all(b"banana").unwrap();
+    assert!(writer.finish().is_err());
+    assert_eq!(inner.written.len(), 8 + 8 + 6 + 2);
+
+    // flush fails halfway through the block, further writes fail and drop writes nothing
+    let mut inner = FlakyWriter {
+        written: Vec::new(),
+        fail_at: Some(8 + 4),
+    };
+    {
+        let mut writer = BwtWriter::new(&mut inner, 100).unwrap();
+        writer.write_all(b"banana").unwrap();
+        assert!(writer.flush().is_err());
+        assert!(writer.write_all(b"bandana").is_err());
+    }
+    assert_eq!(inner.written.len(), 8 + 4);
+
+    // a full block is written before more input is accepted, so that a failed write consumes nothing
+    let mut inner = FlakyWriter {
+        written: Vec::new(),
+        fail_at: Some(8 + 4),
+    };
+    {
+        let mut writer = BwtWriter::new(&mut inner, 4).unwrap();
+        assert_eq!(writer.write(b"bana").unwrap(), 4);
+        assert_eq!(writer.write(b"").unwrap(), 0);
+        assert!(writer.write(b"na").is_err());
+        assert!(writer.write(b"na").is_err());
+    }
+    assert_eq!(inner.written.len(), 8 + 4);
+}
@@ -0,0 +1,92 @@
+use std::ops::Range;
+
+use crate::errors::Error;
+use crate::tests::common::*;
+use crate::token::*;
+
+fn random_words() -> String {
+    let words = ["a", "b", "ab", "ba", "the", "naïve", "日本"];
+    random_text(100..=200, 0..=words.len() - 1)
+        .into_iter()
+        .map(|i| words[i])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn test_whitespace_tokenizer() {
+    let text = "  the  quick\tbrown\n fox ";
+    let tokens: Vec<&str> = Whitespace
+        .tokenize(text)
+        .into_iter()
+        .map(|r| &text[r])
+        .collect();
+    assert_eq!(tokens, ["the", "quick", "brown", "fox"]);
+    assert!(Whitespace.tokenize("   ").is_empty());
+}
+
+#[test]
+fn test_token_suffix_array() {
+    for text in [
+        "",
+        "one",
+        "to be or not to be",
+        "a a a a a b a a",
+        &random_words(),
+    ] {
+        let tsa = TokenSuffixArray::new(text, Whitespace).unwrap();
+        let words: Vec<&str> = tsa.tokens().iter().map(|r| &text[r.clone()]).collect();
+        for (&id, &w) in tsa.token_ids().iter().zip(words.iter()) {
+            assert_eq!(tsa.vocabulary().token(id as u32), w);
+        }
+
+        // suffixes sorted as token sequences
+        let sa = tsa.suffix_array();
+        for k in 1..sa.len() {
+            assert!(words[sa[k - 1] as usize..] < words[sa[k] as usize..]);
+        }
+
+        // queries of up to 3 tokens from the text
+        for start in 0..words.len() {
+            for m in 1..=Ord::min(3, words.len() - start) {
+                let query = words[start..start + m].join(" ");
+                let mut actual = tsa.find(&query);
+                actual.sort_by_key(|r| r.start);
+                let expected: Vec<Range<usize>> = (0..=words.len() - m)
+                    .filter(|&i| words[i..i + m] == words[start..start + m])
+                    .map(|i| tsa.tokens()[i].start..tsa.tokens()[i + m - 1].end)
+                    .collect();
+                assert_eq!(actual, expected);
+            }
+        }
+        assert_eq!(tsa.count("missing"), 0);
+        assert_eq!(tsa.count(""), words.len());
+    }
+}
+
+#[test]
+fn test_token_callback() {
+    // comma separated fields, trimmed
+    let fields = |text: &str| -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        for piece in text.split(',') {
+            let trimmed = piece.trim();
+            let offset = start + piece.find(trimmed).unwrap_or(0);
+            if !trimmed.is_empty() {
+                ranges.push(offset..offset + trimmed.len());
+            }
+            start += piece.len() + 1;
+        }
+        ranges
+    };
+    let text = "new york, paris, new york, tokyo";
+    let tsa = TokenSuffixArray::new(text, fields).unwrap();
+    assert_eq!(tsa.vocabulary().len(), 3);
+    assert_eq!(tsa.find("new york ,paris"), vec![(0..15)]);
+    assert_eq!(tsa.count("new york"), 2);
+    assert_eq!(tsa.count("new"), 0);
+
+    let bad = |_: &str| Vec::from([0..4, 0..100]);
+    assert_eq!(TokenSuffixArray::new(text, bad).err(), Some(Error::IllegalArguments));
+}
@@ -0,0 +1,69 @@
+use crate::unicode::*;
+
+const TEXTS: [&str; 6] = [
+    "",
+    "banana",
+    "naïve café, naïve",
+    "日本語のテキスト、日本の語",
+    "emoji 🦀🦀 crab 🦀 and ÿ vs z",
+    "Grüße, Straße! ß 42 straße",
+];
+
+fn naive_suffixes(text: &str, boundary: Boundary) -> Vec<usize> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut suffixes: Vec<usize> = chars
+        .iter()
+        .enumerate()
+        .filter(|&(k, &(_, c))| match boundary {
+            Boundary::Char => true,
+            Boundary::Word => c.is_alphanumeric() && (k == 0 || !chars[k - 1].1.is_alphanumeric()),
+        })
+        .map(|(_, &(p, _))| p)
+        .collect();
+    // code point order
+    suffixes.sort_by(|&p, &q| text[p..].chars().cmp(text[q..].chars()));
+    suffixes
+}
+
+#[test]
+fn test_str_suffix_array() {
+    for text in TEXTS {
+        for boundary in [Boundary::Char, Boundary::Word] {
+            let sa = StrSuffixArray::with_boundary(text, boundary).unwrap();
+            let actual: Vec<usize> = sa.suffix_array().iter().map(|&p| p as usize).collect();
+            assert_eq!(actual, naive_suffixes(text, boundary));
+        }
+    }
+}
+
+#[test]
+fn test_str_find() {
+    for text in TEXTS {
+        let sa = StrSuffixArray::new(text).unwrap();
+        let words = StrSuffixArray::with_boundary(text, Boundary::Word).unwrap();
+        let patterns = ["", "a", "na", "ïve", "日本", "🦀", "ß", "straße", "zzz"];
+        for pattern in patterns {
+            let mut actual: Vec<usize> = sa.find(pattern).collect();
+            actual.sort();
+            let expected: Vec<usize> = text.match_indices(pattern).map(|(p, _)| p).collect();
+            if pattern.is_empty() {
+                assert_eq!(actual, text.char_indices().map(|(p, _)| p).collect::<Vec<_>>());
+                continue;
+            }
+            // match_indices skips overlapping matches, so compare as a subset plus exact count
+            assert!(expected.iter().all(|p| actual.contains(p)));
+            assert_eq!(
+                actual.len(),
+                (0..text.len())
+                    .filter(|&p| text.is_char_boundary(p) && text[p..].starts_with(pattern))
+                    .count()
+            );
+            for &p in actual.iter() {
+                assert_eq!(&text[p..p + pattern.len()], pattern);
+            }
+            let word_starts: Vec<usize> = words.find(pattern).collect();
+            assert!(word_starts.iter().all(|p| actual.contains(p)));
+            assert_eq!(words.count(pattern), word_starts.len());
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Word-level suffix array over tokens of a text, with a vocabulary to translate queries.
+//!
+//! Tokens are mapped to dense ids by their rank in the sorted vocabulary, so that the suffix array constructed
+//! by [`sais_int`] sorts token sequences lexicographically. Queries are tokenized the
+//! same way as the text, and occurrences are mapped back to byte ranges of the text.
+
+use std::ops::Range;
+
+use crate::common::{max_size, pattern_range};
+use crate::errors::Error;
+use crate::sais32::{sais_int, Result, MAX_LENGTH};
+
+/// Splits a text into byte ranges of tokens.
+pub trait Tokenizer {
+    /// Byte ranges of tokens in `text`, in text order.
+    fn tokenize(&self, text: &str) -> Vec<Range<usize>>;
+}
+
+/// Tokens separated by whitespace.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Whitespace;
+
+impl Tokenizer for Whitespace {
+    fn tokenize(&self, text: &str) -> Vec<Range<usize>> {
+        let mut tokens = Vec::new();
+        let mut start = None;
+        for (p, c) in text.char_indices().chain([(text.len(), ' ')]) {
+            match (start, c.is_whitespace()) {
+                (None, false) => start = Some(p),
+                (Some(s), true) => {
+                    tokens.push(s..p);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        tokens
+    }
+}
+
+/// User callback as tokenizer, e.g. matches of a regex.
+impl<F: Fn(&str) -> Vec<Range<usize>>> Tokenizer for F {
+    fn tokenize(&self, text: &str) -> Vec<Range<usize>> {
+        self(text)
+    }
+}
+
+/// Distinct tokens with dense ids in lexicographical order, looked up by binary search.
+///
+/// Deserialization checks that tokens are sorted without duplicates.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "VocabularyParts"))]
+pub struct Vocabulary {
+    tokens: Vec<String>,
+}
+
+/// Fields of a deserialized vocabulary, before validation.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct VocabularyParts {
+    tokens: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<VocabularyParts> for Vocabulary {
+    type Error = &'static str;
+
+    fn try_from(parts: VocabularyParts) -> std::result::Result<Self, Self::Error> {
+        match parts.tokens.windows(2).all(|pair| pair[0] < pair[1]) {
+            true => Ok(Vocabulary { tokens: parts.tokens }),
+            false => Err("vocabulary tokens not sorted or not distinct"),
+        }
+    }
+}
+
+impl Vocabulary {
+    fn new<'t>(tokens: impl Iterator<Item = &'t str>) -> Self {
+        let mut tokens: Vec<String> = tokens.map(str::to_owned).collect();
+        tokens.sort_unstable();
+        tokens.dedup();
+        Vocabulary { tokens }
+    }
+
+    /// Number of distinct tokens.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Whether the vocabulary is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Id of `token`, or `None` if it is not in the vocabulary.
+    #[inline]
+    pub fn id(&self, token: &str) -> Option<u32> {
+        self.tokens
+            .binary_search_by(|t| t.as_str().cmp(token))
+            .ok()
+            .map(|id| id as u32)
+    }
+
+    /// Token of `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is out of range.
+    #[inline]
+    pub fn token(&self, id: u32) -> &str {
+        &self.tokens[id as usize]
+    }
+}
+
+/// Suffix array over token sequence of a text.
+///
+/// Deserialization borrows the text from the input, and checks that tokens are in the text and the vocabulary
+/// and suffixes are a permutation of tokens, but not their order.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "TokenSuffixArrayParts<'a, K>"))]
+pub struct TokenSuffixArray<'a, K> {
+    text: &'a str,
+    tokenizer: K,
+    tokens: Vec<Range<usize>>,
+    ids: Vec<i32>,
+    vocabulary: Vocabulary,
+    sa: Vec<i32>,
+}
+
+/// Fields of a deserialized token suffix array, before validation.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct TokenSuffixArrayParts<'a, K> {
+    text: &'a str,
+    tokenizer: K,
+    tokens: Vec<Range<usize>>,
+    ids: Vec<i32>,
+    vocabulary: Vocabulary,
+    sa: Vec<i32>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a, K> TryFrom<TokenSuffixArrayParts<'a, K>> for TokenSuffixArray<'a, K> {
+    type Error = &'static str;
+
+    fn try_from(parts: TokenSuffixArrayParts<'a, K>) -> std::result::Result<Self, Self::Error> {
+        let TokenSuffixArrayParts {
+            text,
+            tokenizer,
+            tokens,
+            ids,
+            vocabulary,
+            sa,
+        } = parts;
+        if ids.len() != tokens.len() || sa.len() != tokens.len() {
+            return Err("token ids or suffixes differ in number from tokens");
+        }
+        for (range, &id) in tokens.iter().zip(ids.iter()) {
+            let word = text.get(range.clone());
+            let token = u32::try_from(id)
+                .ok()
+                .filter(|&id| (id as usize) < vocabulary.len());
+            if word.is_none() || word != token.map(|id| vocabulary.token(id)) {
+                return Err("tokens inconsistent with the text or the vocabulary");
+            }
+        }
+        crate::common::inverse_suffix_array(&sa).map_err(|_| "suffixes not a permutation of tokens")?;
+        Ok(TokenSuffixArray {
+            text,
+            tokenizer,
+            tokens,
+            ids,
+            vocabulary,
+            sa,
+        })
+    }
+}
+
+impl<'a, K: Tokenizer> TokenSuffixArray<'a, K> {
+    /// Tokenizes `text` and constructs suffix array of its token sequence.
+    ///
+    /// Returns `Err(Error::IllegalArguments)` if `tokenizer` yields ranges out of text or char boundaries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sais::token::{TokenSuffixArray, Whitespace};
+    ///
+    /// let text = "the cat sat on the mat, the cat ran";
+    /// let tsa = TokenSuffixArray::new(text, Whitespace).unwrap();
+    /// let mut matches = tsa.find("the cat");
+    /// matches.sort_by_key(|range| range.start);
+    /// assert_eq!(matches, [0..7, 24..31]);
+    /// assert_eq!(&text[matches[1].clone()], "the cat");
+    /// assert_eq!(tsa.count("cat sat"), 1);
+    /// assert_eq!(tsa.count("dog"), 0);
+    /// ```
+    pub fn new(text: &'a str, tokenizer: K) -> Result<Self> {
+        let tokens = tokenizer.tokenize(text);
+        let n = max_size(tokens.len(), MAX_LENGTH)?;
+        let mut words = Vec::with_capacity(n);
+        for range in tokens.iter() {
+            words.push(text.get(range.clone()).ok_or(Error::IllegalArguments)?);
+        }
+        let vocabulary = Vocabulary::new(words.iter().copied());
+        let ids = words
+            .iter()
+            .map(|&w| {
+                vocabulary
+                    .id(w)
+                    .map(|id| id as i32)
+                    .ok_or(Error::InternalError)
+            })
+            .collect::<Result<Vec<i32>>>()?;
+
+        let mut sa = vec![0; n];
+        sais_int(&mut ids.clone(), &mut sa, vocabulary.len() as i32)?;
+        Ok(TokenSuffixArray {
+            text,
+            tokenizer,
+            tokens,
+            ids,
+            vocabulary,
+            sa,
+        })
+    }
+
+    /// Indexed text.
+    #[inline]
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Byte ranges of tokens in text order.
+    #[inline]
+    pub fn tokens(&self) -> &[Range<usize>] {
+        &self.tokens
+    }
+
+    /// Token ids in text order.
+    #[inline]
+    pub fn token_ids(&self) -> &[i32] {
+        &self.ids
+    }
+
+    /// Vocabulary of the text.
+    #[inline]
+    pub fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
+    }
+
+    /// Token indices of suffixes in lexicographical order.
+    #[inline]
+    pub fn suffix_array(&self) -> &[i32] {
+        &self.sa
+    }
+
+    /// Translates `query` into token ids, returns `None` if any token is not in the vocabulary.
+    pub fn query_ids(&self, query: &str) -> Option<Vec<i32>> {
+        self.tokenizer
+            .tokenize(query)
+            .into_iter()
+            .map(|range| {
+                query
+                    .get(range)
+                    .and_then(|w| self.vocabulary.id(w))
+                    .map(|id| id as i32)
+            })
+            .collect()
+    }
+
+    /// Rows of the suffix array whose token sequences start with tokens of `query`.
+    pub fn range(&self, query: &str) -> Range<usize> {
+        match self.query_ids(query) {
+            Some(ids) => pattern_range(&self.ids, &self.sa, &ids),
+            None => 0..0,
+        }
+    }
+
+    /// Number of occurrences of `query` as a token sequence.
+    #[inline]
+    pub fn count(&self, query: &str) -> usize {
+        self.range(query).len()
+    }
+
+    /// Byte ranges from the first to the last matched token of `query` occurrences, in suffix array order.
+    ///
+    /// Queries without tokens match the empty range before every token.
+    pub fn find(&self, query: &str) -> Vec<Range<usize>> {
+        let Some(ids) = self.query_ids(query) else {
+            return Vec::new();
+        };
+        let m = ids.len();
+        self.sa[pattern_range(&self.ids, &self.sa, &ids)]
+            .iter()
+            .map(|&i| {
+                let i = i as usize;
+                let start = self.tokens[i].start;
+                let end = if m == 0 { start } else { self.tokens[i + m - 1].end };
+                start..end
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,144 @@
+//! Suffix array of a `str` over char or word boundaries only, sorted in code point order.
+//!
+//! Utf-8 preserves code point order in byte order, so the byte suffix array constructed by
+//! [`sais`] with suffixes starting at continuation bytes filtered out is already sorted.
+//! Suffixes and matches are byte offsets, which are always valid for slicing.
+
+use std::ops::Range;
+
+use crate::common::{max_size, pattern_range};
+use crate::sais32::{sais, Result, MAX_LENGTH};
+
+/// Starting positions of suffixes in a suffix array of `str`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Boundary {
+    /// Every char.
+    Char,
+    /// Alphanumeric chars not preceded by an alphanumeric char.
+    Word,
+}
+
+impl Boundary {
+    fn is_start(self, previous: Option<char>, c: char) -> bool {
+        match self {
+            Boundary::Char => true,
+            Boundary::Word => c.is_alphanumeric() && !previous.is_some_and(char::is_alphanumeric),
+        }
+    }
+}
+
+/// Suffix array of a `str` with suffixes starting at boundaries only.
+///
+/// Deserialization borrows the text from the input, and checks that suffixes are distinct char boundaries
+/// of the text but not their order.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "StrSuffixArrayParts<'a>"))]
+pub struct StrSuffixArray<'a> {
+    text: &'a str,
+    sa: Vec<i32>,
+}
+
+/// Fields of a deserialized suffix array of `str`, before validation.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct StrSuffixArrayParts<'a> {
+    text: &'a str,
+    sa: Vec<i32>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> TryFrom<StrSuffixArrayParts<'a>> for StrSuffixArray<'a> {
+    type Error = &'static str;
+
+    fn try_from(parts: StrSuffixArrayParts<'a>) -> std::result::Result<Self, Self::Error> {
+        let StrSuffixArrayParts { text, sa } = parts;
+        let mut seen = vec![false; text.len()];
+        for &p in sa.iter() {
+            match usize::try_from(p)
+                .ok()
+                .filter(|&p| text.is_char_boundary(p))
+            {
+                Some(p) if p < text.len() && !seen[p] => seen[p] = true,
+                _ => return Err("suffixes not distinct char boundaries of the text"),
+            }
+        }
+        Ok(StrSuffixArray { text, sa })
+    }
+}
+
+impl<'a> StrSuffixArray<'a> {
+    /// Constructs suffix array of `text` over char boundaries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sais::unicode::StrSuffixArray;
+    ///
+    /// let text = "naïve café, naïve";
+    /// let sa = StrSuffixArray::new(text).unwrap();
+    /// let mut offsets: Vec<usize> = sa.find("ïve").collect();
+    /// offsets.sort();
+    /// assert_eq!(offsets, [2, 16]);
+    /// assert_eq!(&text[offsets[1]..], "ïve");
+    /// ```
+    pub fn new(text: &'a str) -> Result<Self> {
+        Self::with_boundary(text, Boundary::Char)
+    }
+
+    /// Constructs suffix array of `text` over the given kind of boundaries.
+    pub fn with_boundary(text: &'a str, boundary: Boundary) -> Result<Self> {
+        let n = max_size(text.len(), MAX_LENGTH)?;
+        let mut sa = vec![0; n];
+        sais(text.as_bytes(), &mut sa, None)?;
+
+        let mut starts = vec![false; n];
+        let mut previous = None;
+        for (p, c) in text.char_indices() {
+            starts[p] = boundary.is_start(previous, c);
+            previous = Some(c);
+        }
+        sa.retain(|&p| starts[p as usize]);
+        Ok(StrSuffixArray { text, sa })
+    }
+
+    /// Indexed text.
+    #[inline]
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Byte offsets of the indexed suffixes in lexicographical order.
+    #[inline]
+    pub fn suffix_array(&self) -> &[i32] {
+        &self.sa
+    }
+
+    /// Number of indexed suffixes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sa.len()
+    }
+
+    /// Whether no suffix is indexed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sa.is_empty()
+    }
+
+    /// Rows of the suffix array whose suffixes start with `pattern`.
+    #[inline]
+    pub fn range(&self, pattern: &str) -> Range<usize> {
+        pattern_range(self.text.as_bytes(), &self.sa, pattern.as_bytes())
+    }
+
+    /// Number of occurrences of `pattern` at indexed boundaries.
+    #[inline]
+    pub fn count(&self, pattern: &str) -> usize {
+        self.range(pattern).len()
+    }
+
+    /// Byte offsets of occurrences of `pattern` at indexed boundaries, in suffix array order.
+    pub fn find(&self, pattern: &str) -> impl Iterator<Item = usize> + '_ {
+        self.sa[self.range(pattern)].iter().map(|&p| p as usize)
+    }
+}